@@ -16,6 +16,7 @@ import Divider from "@material-ui/core/Divider";
 import styled from "@material-ui/core/styles/styled";
 
 import { formatFileSize } from "./util";
+import { Demo } from "./Demos";
 import SmallDialog from "./SmallDialog";
 
 type AutoDeleteDialogProps = {
@@ -62,7 +63,21 @@ async function findFilesWithoutCounterpart() {
       }
     }
   }
-  return filesWithoutCounterpart;
+
+  // Recordings in progress don't have an events file yet
+  const recording = await Promise.all(
+    filesWithoutCounterpart.map(async (file) => {
+      if (!file.endsWith(".dem")) {
+        return false;
+      }
+      try {
+        return (await Demo.create(path.join(demoDir, file))).recording;
+      } catch (e) {
+        return false;
+      }
+    })
+  );
+  return filesWithoutCounterpart.filter((_file, i) => !recording[i]);
 }
 
 const ScrollList = styled(List)({
@@ -0,0 +1,140 @@
+import React from "react";
+
+import Button from "@material-ui/core/Button";
+import DialogContentText from "@material-ui/core/DialogContentText";
+import IconButton from "@material-ui/core/IconButton";
+import LinearProgress from "@material-ui/core/LinearProgress";
+import List from "@material-ui/core/List";
+import ListItem from "@material-ui/core/ListItem";
+import ListItemSecondaryAction from "@material-ui/core/ListItemSecondaryAction";
+import ListItemText from "@material-ui/core/ListItemText";
+import Tooltip from "@material-ui/core/Tooltip";
+import CancelIcon from "@material-ui/icons/Cancel";
+
+import SmallDialog from "./SmallDialog";
+import jobManager, { Job } from "./Jobs";
+
+type BackgroundTasksDialogProps = {
+  ref: React.RefObject<BackgroundTasksDialog>;
+};
+
+type BackgroundTasksDialogState = {
+  open: boolean;
+  jobs: Job[];
+};
+
+function formatStatus(job: Job) {
+  switch (job.status) {
+    case "running":
+      return job.progress === null
+        ? "Running"
+        : `Running, ${Math.round(job.progress * 100)}%`;
+    case "done":
+      return "Done";
+    case "cancelled":
+      return "Cancelled";
+    default:
+      return `Failed: ${job.error}`;
+  }
+}
+
+/**
+ * Lists the scans, uploads and exports that are running or finished
+ * recently, and lets the user cancel the ones that support it.
+ */
+export default class BackgroundTasksDialog extends React.Component<
+  BackgroundTasksDialogProps,
+  BackgroundTasksDialogState
+> {
+  constructor(props: BackgroundTasksDialogProps) {
+    super(props);
+    this.state = { open: false, jobs: jobManager.getJobs() };
+  }
+
+  componentDidMount() {
+    jobManager.on("changed", this.onJobsChanged);
+  }
+
+  componentWillUnmount() {
+    jobManager.off("changed", this.onJobsChanged);
+  }
+
+  onJobsChanged = (jobs: Job[]) => {
+    this.setState({ jobs });
+  };
+
+  open = () => {
+    this.setState({ open: true });
+  };
+
+  close = () => {
+    this.setState({ open: false });
+  };
+
+  render() {
+    const { open, jobs } = this.state;
+    return (
+      <SmallDialog
+        title="Background tasks"
+        open={open}
+        onClose={this.close}
+        maxWidth="sm"
+        actions={
+          <>
+            <Button
+              disabled={jobs.every((job) => job.status === "running")}
+              onClick={() => jobManager.clearFinished()}
+            >
+              Clear finished
+            </Button>
+            <Button variant="contained" onClick={this.close}>
+              Close
+            </Button>
+          </>
+        }
+      >
+        {jobs.length === 0 ? (
+          <DialogContentText>No tasks have run yet.</DialogContentText>
+        ) : (
+          <List dense>
+            {[...jobs].reverse().map((job) => (
+              <ListItem key={job.id}>
+                <ListItemText
+                  primary={job.description}
+                  secondary={
+                    <>
+                      {formatStatus(job)}
+                      {job.status === "running" && (
+                        <LinearProgress
+                          variant={
+                            job.progress === null
+                              ? "indeterminate"
+                              : "determinate"
+                          }
+                          value={(job.progress ?? 0) * 100}
+                        />
+                      )}
+                    </>
+                  }
+                  secondaryTypographyProps={{ component: "div" }}
+                />
+                {job.cancellable && (
+                  <ListItemSecondaryAction>
+                    <Tooltip title="Cancel">
+                      <IconButton
+                        edge="end"
+                        onClick={() => jobManager.cancel(job.id)}
+                      >
+                        <CancelIcon />
+                      </IconButton>
+                    </Tooltip>
+                  </ListItemSecondaryAction>
+                )}
+              </ListItem>
+            ))}
+          </List>
+        )}
+      </SmallDialog>
+    );
+  }
+}
@@ -0,0 +1,96 @@
+import React from "react";
+
+import Button from "@material-ui/core/Button";
+import TextField from "@material-ui/core/TextField";
+import DialogContentText from "@material-ui/core/DialogContentText";
+
+import { Demo } from "./Demos";
+import SmallDialog from "./SmallDialog";
+import { formatDemoName, getRenameTemplate } from "./RenameTemplate";
+
+type BulkRenameDialogProps = {
+  onClose: () => void;
+  onConfirm: (demos: Demo[], template: string) => void;
+  ref: React.RefObject<BulkRenameDialog>;
+};
+
+type BulkRenameDialogState = {
+  demos: Demo[];
+  template: string;
+  open: boolean;
+};
+
+export default class BulkRenameDialog extends React.Component<
+  BulkRenameDialogProps,
+  BulkRenameDialogState
+> {
+  constructor(props: BulkRenameDialogProps) {
+    super(props);
+    this.state = { demos: [], template: getRenameTemplate(), open: false };
+  }
+
+  close = () => {
+    this.setState({
+      open: false,
+    });
+  };
+
+  open = (demos: Demo[]) => {
+    this.setState({
+      demos,
+      template: getRenameTemplate(),
+      open: true,
+    });
+  };
+
+  render() {
+    const { onClose, onConfirm } = this.props;
+    const { open, demos, template } = this.state;
+    const preview =
+      demos.length > 0 ? `${formatDemoName(demos[0], template)}.dem` : "";
+
+    return (
+      <SmallDialog
+        title={`Rename ${demos.length} demos`}
+        open={open}
+        onClose={onClose}
+        maxWidth="sm"
+        actions={
+          <>
+            <Button variant="contained" onClick={onClose}>
+              Cancel
+            </Button>
+            <Button
+              variant="contained"
+              color="primary"
+              disabled={template.trim() === ""}
+              onClick={() => {
+                onConfirm(demos, template);
+              }}
+            >
+              Rename
+            </Button>
+          </>
+        }
+      >
+        <DialogContentText>
+          Available placeholders: {"{map}"}, {"{date}"}, {"{time}"},{" "}
+          {"{server}"} and {"{player}"}. Events files are renamed along with
+          their demos.
+        </DialogContentText>
+        <TextField
+          required
+          label="Template"
+          variant="outlined"
+          value={template}
+          helperText={preview !== "" ? `Example: ${preview}` : undefined}
+          onChange={(e) => {
+            this.setState({ template: e.target.value });
+          }}
+          spellCheck={false}
+          fullWidth
+        />
+      </SmallDialog>
+    );
+  }
+}
@@ -0,0 +1,110 @@
+import cfg from "electron-cfg";
+import log from "electron-log";
+import keytar from "keytar";
+
+const SERVICE = "DemoMan";
+
+// Used where the OS keyring isn't available, e.g. on Linux without
+// a Secret Service provider
+const FALLBACK_KEY = "credentials";
+
+// Settings that used to be stored in plain text in the settings file
+const LEGACY_SETTINGS = ["demostf_api_key", "steam_api_key", "gist_token"];
+
+// Keyring lookups can be slow, values are only read once per session
+const cache = new Map<string, string>();
+
+function getFallbackCredentials(): Record<string, string> {
+  return cfg.get(FALLBACK_KEY, {});
+}
+
+function setFallbackCredential(name: string, value?: string) {
+  const credentials = { ...getFallbackCredentials() };
+  if (value === undefined) {
+    delete credentials[name];
+  } else {
+    credentials[name] = value;
+  }
+  cfg.set(FALLBACK_KEY, credentials);
+}
+
+export function getRconServerCredentialName(serverName: string) {
+  return `rcon_server:${serverName}`;
+}
+
+/**
+ * Returns the stored secret, or an empty string if none is set.
+ * Secrets are kept in the OS keyring, or in the settings file if the
+ * keyring can't be used.
+ */
+export async function getCredential(name: string) {
+  const cached = cache.get(name);
+  if (cached !== undefined) {
+    return cached;
+  }
+  let value: string | null = null;
+  try {
+    value = await keytar.getPassword(SERVICE, name);
+  } catch (e) {
+    log.warn(`Error reading ${name} from the keyring: ${e}`);
+  }
+  const result = value ?? getFallbackCredentials()[name] ?? "";
+  cache.set(name, result);
+  return result;
+}
+
+// Removes the secret if the value is empty
+export async function setCredential(name: string, value: string) {
+  log.info(`${value === "" ? "Clearing" : "Storing"} credential ${name}`);
+  cache.set(name, value);
+  try {
+    if (value === "") {
+      await keytar.deletePassword(SERVICE, name);
+    } else {
+      await keytar.setPassword(SERVICE, name, value);
+    }
+    setFallbackCredential(name);
+  } catch (e) {
+    log.warn(`Error writing ${name} to the keyring, using settings: ${e}`);
+    setFallbackCredential(name, value === "" ? undefined : value);
+  }
+}
+
+export function clearCredential(name: string) {
+  return setCredential(name, "");
+}
+
+/**
+ * Moves API keys and RCON server passwords that older versions stored
+ * in plain text into the credential store.
+ */
+export async function migrateCredentials() {
+  for (let i = 0; i < LEGACY_SETTINGS.length; i += 1) {
+    const key = LEGACY_SETTINGS[i];
+    const value: string = cfg.get(key, "");
+    if (value !== "") {
+      // eslint-disable-next-line no-await-in-loop
+      await setCredential(key, value);
+    }
+    if (cfg.has(key)) {
+      cfg.delete(key);
+    }
+  }
+  const servers: { name: string; password?: string }[] = cfg.get(
+    "rcon_servers",
+    []
+  );
+  if (servers.some((server) => server.password !== undefined)) {
+    for (let i = 0; i < servers.length; i += 1) {
+      const { name, password } = servers[i];
+      if (password !== undefined) {
+        // eslint-disable-next-line no-await-in-loop
+        await setCredential(getRconServerCredentialName(name), password);
+      }
+    }
+    cfg.set(
+      "rcon_servers",
+      servers.map(({ password: _password, ...server }) => server)
+    );
+  }
+}
@@ -11,6 +11,7 @@ import MapIcon from "@material-ui/icons/Map";
 import PersonIcon from "@material-ui/icons/Person";
 import StorageIcon from "@material-ui/icons/Storage";
 import InsertDriveFileIcon from "@material-ui/icons/InsertDriveFile";
+import CodeIcon from "@material-ui/icons/Code";
 
 import { Demo } from "./Demos";
 import { DemoHeader } from "./DemoHeader";
@@ -78,6 +79,16 @@ export default function DemoDetailsList(props: DemoDetailsListProps) {
           <ListItemText>{formatFileSize(demo.filesize)}</ListItemText>
         </ListItem>
       </Tooltip>
+      <Tooltip title="Demo protocol / network protocol" placement="left" arrow>
+        <ListItem>
+          <ListItemIcon>
+            <CodeIcon />
+          </ListItemIcon>
+          <ListItemText>
+            {demoHeader.demoVersion} / {demoHeader.netVersion}
+          </ListItemText>
+        </ListItem>
+      </Tooltip>
     </List>
   );
 }
@@ -1,5 +1,9 @@
 import React from "react";
-import { shell } from "electron";
+import fs from "fs";
+import path from "path";
+import { clipboard, remote, shell } from "electron";
+import cfg from "electron-cfg";
+import log from "electron-log";
 
 import Paper from "@material-ui/core/Paper";
 import Grid from "@material-ui/core/Grid";
@@ -8,19 +12,42 @@ import Tooltip from "@material-ui/core/Tooltip";
 import EditIcon from "@material-ui/icons/Edit";
 import DeleteOutlineIcon from "@material-ui/icons/DeleteOutline";
 import FolderOpenIcon from "@material-ui/icons/FolderOpen";
+import GetAppIcon from "@material-ui/icons/GetApp";
+import Menu from "@material-ui/core/Menu";
+import MenuItem from "@material-ui/core/MenuItem";
 import Button from "@material-ui/core/Button";
 import styled from "@material-ui/core/styles/styled";
+import Typography from "@material-ui/core/Typography";
 
 import { Demo } from "./Demos";
 import { DemoHeader } from "./DemoHeader";
 import DemoEvent from "./DemoEvent";
 import EventTable from "./EventTable";
 import DemoDetailsList from "./DemoDetailsList";
+import PlaybackControls from "./PlaybackControls";
 import FullscreenDialog from "./FullscreenDialog";
 import EditEventDialog from "./EditEventDialog";
 import EventTableEntry from "./EventTableEntry";
 import DeleteDialog from "./DeleteDialog";
 import RenameDialog from "./RenameDialog";
+import {
+  formatEventsAsMirvScript,
+  formatEventsAsRecordingVdm,
+  formatEventsAsText,
+} from "./ExportEvents";
+import generateReport from "./DemoReport";
+import { getVdmPath } from "./Vdm";
+import sharePaste from "./Paste";
+import getMapThumbnail from "./MapThumbnails";
+import setPresence from "./DiscordRpc";
+import repairDemo from "./DemoRepair";
+import rconConnection from "./Rcon";
+import { skipToTick } from "./GameCommands";
+import playbackSync, { PlaybackPosition } from "./PlaybackSync";
+import { getDemosTfUrl } from "./DemosTf";
+import uploadQueue from "./UploadQueue";
+import jobManager from "./Jobs";
+import { getCredential } from "./Credentials";
 
 const GroupIconButton = styled(Button)({ padding: "11px" });
 
@@ -36,6 +63,11 @@ type DemoDetailsState = {
   events: EventTableEntry[];
   nextAvailableID: number;
   deleteDialogOpen: boolean;
+  exportMenuAnchor: Element | null;
+  mapThumbnail: string | undefined;
+  gameConnected: boolean;
+  // Tick the game is at while it plays this demo
+  playbackTick: number | null;
 };
 
 export default class DemoDetails extends React.Component<
@@ -46,6 +78,9 @@ export default class DemoDetails extends React.Component<
 
   private renameDialog: React.RefObject<RenameDialog>;
 
+  // Whether the playback position is being polled while the view is open
+  private syncingPlayback = false;
+
   constructor(props: DemoDetailsProps) {
     super(props);
     this.state = {
@@ -55,14 +90,58 @@ export default class DemoDetails extends React.Component<
       events: [],
       nextAvailableID: 0,
       deleteDialogOpen: false,
+      exportMenuAnchor: null,
+      mapThumbnail: undefined,
+      gameConnected: rconConnection.connected,
+      playbackTick: null,
     };
     this.editEventDialog = React.createRef();
     this.renameDialog = React.createRef();
   }
 
+  componentDidMount() {
+    rconConnection.on("connected", this.gameConnectionChanged);
+    rconConnection.on("disconnected", this.gameConnectionChanged);
+    playbackSync.on("position", this.playbackPositionChanged);
+  }
+
+  componentWillUnmount() {
+    rconConnection.off("connected", this.gameConnectionChanged);
+    rconConnection.off("disconnected", this.gameConnectionChanged);
+    playbackSync.off("position", this.playbackPositionChanged);
+    this.stopPlaybackSync();
+  }
+
+  gameConnectionChanged = () => {
+    this.setState({
+      gameConnected: rconConnection.connected,
+      playbackTick: null,
+    });
+  };
+
+  playbackPositionChanged = (position: PlaybackPosition | null) => {
+    const { demo } = this.state;
+    // playdemo paths are relative to the tf directory, so only the file
+    // names are compared
+    const playing =
+      position !== null &&
+      demo !== null &&
+      path.basename(position.filename).toLowerCase() ===
+        path.basename(demo.filename).toLowerCase();
+    this.setState({ playbackTick: playing ? position?.tick ?? null : null });
+  };
+
+  stopPlaybackSync = () => {
+    if (this.syncingPlayback) {
+      playbackSync.stop();
+      this.syncingPlayback = false;
+    }
+  };
+
   close = () => {
     const { onClose } = this.props;
-    this.setState({ open: false });
+    this.stopPlaybackSync();
+    this.setState({ open: false, playbackTick: null });
     onClose();
   };
 
@@ -79,7 +158,29 @@ export default class DemoDetails extends React.Component<
       demoHeader: header,
       open: true,
       nextAvailableID: i,
+      mapThumbnail: undefined,
     });
+    this.loadMapThumbnail(demo);
+    if (!this.syncingPlayback) {
+      playbackSync.start();
+      this.syncingPlayback = true;
+    }
+    setPresence(`Reviewing ${header.mapName} demo`, demo.getShortName());
+  };
+
+  loadMapThumbnail = async (demo: Demo) => {
+    let mapThumbnail;
+    try {
+      mapThumbnail = await getMapThumbnail(demo.header.mapName);
+    } catch (e) {
+      log.warn(`Error loading map thumbnail: ${e}`);
+      return;
+    }
+    // Make sure the user hasn't opened another demo in the meantime
+    const { demo: currentDemo } = this.state;
+    if (currentDemo === demo) {
+      this.setState({ mapThumbnail });
+    }
   };
 
   writeEvents = () => {
@@ -129,6 +230,15 @@ export default class DemoDetails extends React.Component<
     this.writeEvents();
   };
 
+  skipToEvent = async (event: EventTableEntry) => {
+    try {
+      await skipToTick(event.event.tick, false);
+    } catch (e) {
+      log.error(`Error skipping to tick ${event.event.tick}: ${e}`);
+      remote.dialog.showErrorBox("Could not skip to the event", e.message);
+    }
+  };
+
   editEvent = (event: EventTableEntry) => {
     this.editOrAddEvent(event, true);
   };
@@ -189,11 +299,117 @@ export default class DemoDetails extends React.Component<
     this.renameDialogClose();
   };
 
+  closeExportMenu = () => {
+    this.setState({ exportMenuAnchor: null });
+  };
+
+  exportToFile = (
+    defaultPath: string,
+    filter: Electron.FileFilter,
+    content: string
+  ) => {
+    this.closeExportMenu();
+    const exportPath = remote.dialog.showSaveDialogSync({
+      title: "Export",
+      defaultPath,
+      filters: [filter],
+    });
+    // This happens when the user cancels the dialog
+    if (exportPath === undefined) {
+      return;
+    }
+    log.info(`Exporting to ${exportPath}`);
+    fs.writeFileSync(exportPath, content);
+  };
+
+  repairDemo = async () => {
+    const { demo } = this.state;
+    if (demo === null) {
+      return;
+    }
+    const outputPath = remote.dialog.showSaveDialogSync({
+      title: "Save repaired demo",
+      defaultPath: demo.filename.replace(/\.dem$/i, "_repaired.dem"),
+      filters: [{ name: "Demo", extensions: ["dem"] }],
+    });
+    // This happens when the user cancels the dialog
+    if (outputPath === undefined) {
+      return;
+    }
+    let result;
+    try {
+      result = await jobManager.run(
+        "repair",
+        `Repairing ${demo.getShortName()}`,
+        () => repairDemo(demo, outputPath)
+      );
+    } catch (e) {
+      log.error(`Error repairing demo: ${e}`);
+      remote.dialog.showErrorBox("Could not repair demo", e.message);
+      return;
+    }
+    const { numTicks, numFrames, bytesDropped } = result;
+    remote.dialog.showMessageBox({
+      type: "info",
+      title: "Demo repaired",
+      message: `Recovered ${numTicks} ticks in ${numFrames} frames.`,
+      detail:
+        bytesDropped > 0
+          ? `${bytesDropped} bytes of incomplete data at the end were dropped.`
+          : "No data had to be dropped.",
+    });
+  };
+
+  uploadToDemosTf = async () => {
+    const { demo } = this.state;
+    this.closeExportMenu();
+    if (demo === null) {
+      return;
+    }
+    if ((await getCredential("demostf_api_key")) === "") {
+      remote.dialog.showErrorBox(
+        "No demos.tf API key",
+        "Enter your demos.tf API key in the settings to upload demos."
+      );
+      return;
+    }
+    uploadQueue.add(demo, "demostf");
+  };
+
+  shareEvents = async () => {
+    const { demo } = this.state;
+    this.closeExportMenu();
+    if (demo === null) {
+      return;
+    }
+    try {
+      const url = await sharePaste(
+        demo.getShortName(),
+        formatEventsAsText(demo)
+      );
+      clipboard.writeText(url);
+    } catch (e) {
+      log.error(`Error sharing events: ${e}`);
+      remote.dialog.showErrorBox("Could not share events", e.message);
+    }
+  };
+
   render() {
-    const { demo, demoHeader, open, events, deleteDialogOpen } = this.state;
+    const {
+      demo,
+      demoHeader,
+      open,
+      events,
+      deleteDialogOpen,
+      exportMenuAnchor,
+      mapThumbnail,
+      gameConnected,
+      playbackTick,
+    } = this.state;
     if (demo === null || demoHeader === null) {
       return null;
     }
+    const demosTfUrl = getDemosTfUrl(demo);
     return (
       <>
         <FullscreenDialog
@@ -222,13 +438,60 @@ export default class DemoDetails extends React.Component<
                     width: "320px",
                     height: "200px",
                     backgroundColor: "#666",
+                    backgroundImage: mapThumbnail && `url("${mapThumbnail}")`,
+                    backgroundSize: "cover",
+                    backgroundPosition: "center",
                     textAlign: "center",
                   }}
                 >
-                  <h2 style={{ margin: "0px" }}>{demoHeader.mapName}</h2> <br />
-                  (Map thumbnail coming soon&trade;)
+                  <h2 style={{ margin: "0px" }}>{demoHeader.mapName}</h2>
+                  {mapThumbnail === undefined && (
+                    <>
+                      <br />
+                      {cfg.get("map_thumbnail_url", "") === ""
+                        ? "(Set a map thumbnail source in the settings)"
+                        : "(No map thumbnail available)"}
+                    </>
+                  )}
                 </div>
               </Grid>
+              {!demo.isTf2Demo() && (
+                <Grid item>
+                  <Typography color="error">
+                    This demo was recorded in an unsupported game (
+                    {demoHeader.gameDir}).
+                  </Typography>
+                </Grid>
+              )}
+              {demo.isTf2Demo() && demo.isOldProtocol() && (
+                <Grid item>
+                  <Typography color="error">
+                    This demo was recorded with an older version of TF2 than
+                    DemoMan supports (protocol {demoHeader.demoVersion} /{" "}
+                    {demoHeader.netVersion}). The current game can&apos;t play
+                    it, so skipping to events and playlists won&apos;t work.
+                  </Typography>
+                </Grid>
+              )}
+              {demo.isTf2Demo() && demo.isNewProtocol() && (
+                <Grid item>
+                  <Typography color="error">
+                    This demo was recorded with a newer version of TF2 than
+                    DemoMan supports (protocol {demoHeader.demoVersion} /{" "}
+                    {demoHeader.netVersion}). Some information may be shown
+                    incorrectly.
+                  </Typography>
+                </Grid>
+              )}
+              {demoHeader.numTicks === 0 && !demo.recording && (
+                <Grid item>
+                  <Typography color="error">
+                    This demo was not finished properly, e.g. because the game
+                    crashed.{" "}
+                    <Button onClick={this.repairDemo}>Repair...</Button>
+                  </Typography>
+                </Grid>
+              )}
               <Grid item>
                 <DemoDetailsList demo={demo} demoHeader={demoHeader} />
               </Grid>
@@ -257,15 +520,123 @@ export default class DemoDetails extends React.Component<
                       <FolderOpenIcon />
                     </GroupIconButton>
                   </Tooltip>
+                  <Tooltip title="Export">
+                    <GroupIconButton
+                      onClick={(event: React.MouseEvent<HTMLElement>) => {
+                        this.setState({
+                          exportMenuAnchor: event.currentTarget,
+                        });
+                      }}
+                    >
+                      <GetAppIcon />
+                    </GroupIconButton>
+                  </Tooltip>
                 </ButtonGroup>
+                <Menu
+                  anchorEl={exportMenuAnchor}
+                  getContentAnchorEl={null}
+                  keepMounted
+                  open={exportMenuAnchor !== null}
+                  onClose={this.closeExportMenu}
+                  anchorOrigin={{
+                    vertical: "bottom",
+                    horizontal: "center",
+                  }}
+                  transformOrigin={{
+                    vertical: "top",
+                    horizontal: "center",
+                  }}
+                >
+                  <MenuItem
+                    onClick={() => {
+                      clipboard.writeText(formatEventsAsText(demo));
+                      this.closeExportMenu();
+                    }}
+                  >
+                    Copy events as text
+                  </MenuItem>
+                  <MenuItem onClick={this.shareEvents}>
+                    Share events and copy link
+                  </MenuItem>
+                  {demosTfUrl !== undefined ? (
+                    <MenuItem
+                      onClick={() => {
+                        clipboard.writeText(demosTfUrl);
+                        this.closeExportMenu();
+                      }}
+                    >
+                      Copy demos.tf link
+                    </MenuItem>
+                  ) : (
+                    <MenuItem onClick={this.uploadToDemosTf}>
+                      Upload to demos.tf
+                    </MenuItem>
+                  )}
+                  <MenuItem
+                    onClick={() => {
+                      this.exportToFile(
+                        `${demo.getShortName()}.html`,
+                        { name: "HTML", extensions: ["html"] },
+                        generateReport(demo)
+                      );
+                    }}
+                  >
+                    HTML report...
+                  </MenuItem>
+                  <MenuItem
+                    onClick={() => {
+                      this.exportToFile(
+                        `${demo.getShortName()}_mirv.cfg`,
+                        { name: "Config", extensions: ["cfg"] },
+                        formatEventsAsMirvScript(demo)
+                      );
+                    }}
+                  >
+                    HLAE recording script...
+                  </MenuItem>
+                  <MenuItem
+                    onClick={() => {
+                      this.exportToFile(
+                        getVdmPath(demo),
+                        { name: "VDM", extensions: ["vdm"] },
+                        formatEventsAsRecordingVdm(demo, "svr")
+                      );
+                    }}
+                  >
+                    Source Video Render VDM...
+                  </MenuItem>
+                  <MenuItem
+                    onClick={() => {
+                      this.exportToFile(
+                        getVdmPath(demo),
+                        { name: "VDM", extensions: ["vdm"] },
+                        formatEventsAsRecordingVdm(demo, "lawena")
+                      );
+                    }}
+                  >
+                    Lawena VDM...
+                  </MenuItem>
+                </Menu>
               </Grid>
+              {gameConnected && (
+                <Grid item>
+                  <PlaybackControls
+                    key={demo.filename}
+                    demo={demo}
+                    playbackTick={playbackTick}
+                  />
+                </Grid>
+              )}
             </Grid>
             <Grid item xs={6}>
               <Paper elevation={3} style={{ padding: "5px" }}>
                 <EventTable
+                  demo={demo}
                   data={events}
                   editEvent={this.editEvent}
                   addEvent={this.addEvent}
+                  skipToEvent={gameConnected ? this.skipToEvent : undefined}
+                  playbackTick={playbackTick}
                 />
               </Paper>
             </Grid>
@@ -12,9 +12,12 @@ export interface DemoHeader {
 }
 
 export class InvalidDemoFileError extends Error {
-  constructor() {
+  constructor(reason?: string) {
     super();
     this.message = "Invalid demo file";
+    if (reason !== undefined) {
+      this.message += `: ${reason}`;
+    }
     this.name = "InvalidDemoFileError";
   }
 }
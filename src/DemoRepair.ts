@@ -0,0 +1,120 @@
+import fs from "fs";
+import log from "electron-log";
+
+import { Demo, HEADER_SIZE } from "./Demos";
+
+// See demofile.h in the Source SDK
+export enum DemoCommand {
+  Signon = 1,
+  Packet = 2,
+  SyncTick = 3,
+  ConsoleCmd = 4,
+  UserCmd = 5,
+  DataTables = 6,
+  Stop = 7,
+  StringTables = 8,
+}
+
+// Size of democmdinfo_t, which precedes the data of packet frames
+const CMD_INFO_SIZE = 76;
+
+// Each frame starts with the command and the tick
+const FRAME_HEADER_SIZE = 5;
+
+export interface RepairResult {
+  numTicks: number;
+  numFrames: number;
+  bytesDropped: number;
+}
+
+// Returns the size of the frame at the offset,
+// or undefined if it is incomplete or invalid.
+export function getFrameSize(buf: Buffer, offset: number) {
+  if (offset + FRAME_HEADER_SIZE > buf.length) {
+    return undefined;
+  }
+  let pos = offset + FRAME_HEADER_SIZE;
+  switch (buf.readUInt8(offset)) {
+    case DemoCommand.Signon:
+    case DemoCommand.Packet:
+      // Command info and two sequence numbers
+      pos += CMD_INFO_SIZE + 8;
+      break;
+    case DemoCommand.SyncTick:
+      return FRAME_HEADER_SIZE;
+    case DemoCommand.UserCmd:
+      // Outgoing sequence number
+      pos += 4;
+      break;
+    case DemoCommand.ConsoleCmd:
+    case DemoCommand.DataTables:
+    case DemoCommand.StringTables:
+      break;
+    default:
+      return undefined;
+  }
+  if (pos + 4 > buf.length) {
+    return undefined;
+  }
+  const length = buf.readInt32LE(pos);
+  pos += 4;
+  if (length < 0 || pos + length > buf.length) {
+    return undefined;
+  }
+  return pos + length - offset;
+}
+
+/**
+ * Writes a copy of the demo to outputPath that ends after the last
+ * complete frame, with the tick count, frame count and playback time in
+ * the header filled in. Demos of games that crashed while recording
+ * usually end in the middle of a frame and have a tick count of 0.
+ */
+export default async function repairDemo(
+  demo: Demo,
+  outputPath: string
+): Promise<RepairResult> {
+  if (outputPath === demo.filename) {
+    throw new Error("The repaired demo must not replace the original");
+  }
+  log.info(`Repairing demo ${demo.filename}`);
+  const buf = await fs.promises.readFile(demo.filename);
+
+  let offset = HEADER_SIZE;
+  let signonEnd: number | undefined;
+  let numTicks = 0;
+  let numFrames = 0;
+  let size = getFrameSize(buf, offset);
+  while (size !== undefined) {
+    if (signonEnd === undefined && buf[offset] !== DemoCommand.Signon) {
+      signonEnd = offset;
+    }
+    numTicks = Math.max(numTicks, buf.readInt32LE(offset + 1));
+    numFrames += 1;
+    offset += size;
+    size = getFrameSize(buf, offset);
+  }
+  // A stop frame is appended below, an existing one doesn't count as lost
+  const hasStop = buf[offset] === DemoCommand.Stop;
+
+  const header = Buffer.from(buf.slice(0, HEADER_SIZE));
+  header.writeFloatLE(numTicks * demo.getTickInterval(), HEADER_SIZE - 16);
+  header.writeInt32LE(numTicks, HEADER_SIZE - 12);
+  header.writeInt32LE(numFrames, HEADER_SIZE - 8);
+  if (header.readInt32LE(HEADER_SIZE - 4) === 0) {
+    header.writeInt32LE((signonEnd ?? offset) - HEADER_SIZE, HEADER_SIZE - 4);
+  }
+  const stop = Buffer.alloc(FRAME_HEADER_SIZE);
+  stop.writeUInt8(DemoCommand.Stop, 0);
+  stop.writeInt32LE(numTicks, 1);
+
+  await fs.promises.writeFile(
+    outputPath,
+    Buffer.concat([header, buf.slice(HEADER_SIZE, offset), stop])
+  );
+  const bytesDropped = buf.length - offset - (hasStop ? FRAME_HEADER_SIZE : 0);
+  log.info(
+    `Repaired demo has ${numTicks} ticks, dropped ${bytesDropped} bytes`
+  );
+  return { numTicks, numFrames, bytesDropped };
+}
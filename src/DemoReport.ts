@@ -0,0 +1,68 @@
+import { Demo } from "./Demos";
+import { formatFileSize, formatPlaybackTime } from "./util";
+
+function escapeHtml(text: string) {
+  return text
+    .replace(/&/g, "&amp;")
+    .replace(/</g, "&lt;")
+    .replace(/>/g, "&gt;")
+    .replace(/"/g, "&quot;");
+}
+
+export default function generateReport(demo: Demo): string {
+  const { header } = demo;
+  const details: [string, string][] = [
+    ["Map", header.mapName],
+    ["Player", header.clientName],
+    ["Server", header.serverName],
+    [
+      "Playback time",
+      `${formatPlaybackTime(header.playbackTime)} (${header.numTicks} ticks)`,
+    ],
+    ["Recording date", new Date(demo.birthtime).toLocaleString()],
+    ["File size", formatFileSize(demo.filesize)],
+  ];
+  const detailRows = details
+    .map(
+      ([key, value]) =>
+        `<tr><th>${key}</th><td>${escapeHtml(value)}</td></tr>`
+    )
+    .join("\n");
+  const eventRows = [...demo.events]
+    .sort((a, b) => a.tick - b.tick)
+    .map(
+      (event) =>
+        `<tr><td>${formatPlaybackTime(demo.tickToSeconds(event.tick))}</td>` +
+        `<td>${event.tick}</td>` +
+        `<td>${escapeHtml(event.name)}</td>` +
+        `<td>${escapeHtml(event.value)}</td></tr>`
+    )
+    .join("\n");
+  const title = escapeHtml(demo.getShortName());
+
+  return `<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>${title}</title>
+<style>
+body { font-family: sans-serif; background: #303030; color: #fafafa; margin: 2rem; }
+table { border-collapse: collapse; margin-bottom: 2rem; }
+th, td { text-align: left; padding: 4px 12px; border-bottom: 1px solid #555; }
+</style>
+</head>
+<body>
+<h1>${title}</h1>
+<table>
+${detailRows}
+</table>
+<h2>Events</h2>
+<table>
+<tr><th>Time</th><th>Tick</th><th>Type</th><th>Value</th></tr>
+${eventRows}
+</table>
+<p>Generated by DemoMan</p>
+</body>
+</html>
+`;
+}
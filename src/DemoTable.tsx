@@ -1,11 +1,15 @@
 import React, { PureComponent } from "react";
-import { shell } from "electron";
+import fs from "fs";
+import path from "path";
+import { remote, shell } from "electron";
 
 import DataTable, {
   createTheme,
   defaultThemes,
+  IDataTableColumn,
 } from "react-data-table-component";
 import cfg from "electron-cfg";
+import log from "electron-log";
 import merge from "deepmerge";
 
 import ArrowDownward from "@material-ui/icons/ArrowDownward";
@@ -14,56 +18,135 @@ import RefreshIcon from "@material-ui/icons/Refresh";
 import SettingsIcon from "@material-ui/icons/Settings";
 import InfoIcon from "@material-ui/icons/InfoOutlined";
 import MoreHorizIcon from "@material-ui/icons/MoreHoriz";
+import VideogameAssetIcon from "@material-ui/icons/VideogameAsset";
+import WarningIcon from "@material-ui/icons/Warning";
 import Tooltip from "@material-ui/core/Tooltip";
 import Menu from "@material-ui/core/Menu";
 import MenuItem from "@material-ui/core/MenuItem";
 import blue from "@material-ui/core/colors/blue";
+import red from "@material-ui/core/colors/red";
 import Paper from "@material-ui/core/Paper";
 import InputBase from "@material-ui/core/InputBase";
 import ClearIcon from "@material-ui/icons/Clear";
 import Divider from "@material-ui/core/Divider";
+import Button from "@material-ui/core/Button";
 
 import loading from "../assets/loading.gif";
 
-import { Demo, getDemosInDirectory } from "./Demos";
+import {
+  Demo,
+  getDemosInDirectory,
+  getSkippedFiles,
+  SkippedFile,
+} from "./Demos";
+import { InvalidDemoFileError } from "./DemoHeader";
 import { formatFileSize, formatPlaybackTime } from "./util";
 import { getPreferredTheme } from "./theme";
 import { DemoListInfo } from "./InfoDialog";
 import convertPrecEvents from "./ConvertPrecEvents";
+import { formatEventsForClipTools } from "./ExportEvents";
+import { createDiagnosticBundle } from "./Diagnostics";
+import DirectoryWatcher from "./DirectoryWatcher";
+import rconConnection from "./Rcon";
+import { getSessionName, groupIntoSessions, Session } from "./Sessions";
+import {
+  backupEvents,
+  importLegacyEvents,
+  restoreEvents,
+} from "./EventsBackup";
+import BulkRenameDialog from "./BulkRenameDialog";
+import QuarantineDialog from "./QuarantineDialog";
+import RconConsoleDialog from "./RconConsoleDialog";
+import SkippedFilesDialog from "./SkippedFilesDialog";
+import { connectToGame } from "./LaunchTf2";
+import { playPlaylist } from "./Playlist";
+import jobManager from "./Jobs";
+import BackgroundTasksDialog from "./BackgroundTasksDialog";
+import UploadQueueDialog from "./UploadQueueDialog";
+import {
+  getRenameTemplate,
+  renameDemoByTemplate,
+  renameDemosByTemplate,
+} from "./RenameTemplate";
+import {
+  getDirectoryPreferences,
+  setDirectoryPreferences,
+} from "./DirectoryPreferences";
 
 // Fixes an ESLint false positive
 /* eslint-disable react/no-unused-prop-types */
 interface DemoListEntry {
   filename: string;
+  // Game directory from the header, "tf" for TF2
+  game: string;
+  supportedGame: boolean;
+  demoVersion: number;
+  netVersion: number;
+  // Whether the protocol is older or newer than the ones DemoMan knows
+  protocolSupport: "older" | "newer" | "supported";
   map: string;
   playbackTime: number;
+  recording: boolean;
   player: string;
   server: string;
   numEvents: number;
   numTicks: number;
   birthtime: number;
   filesize: number;
+  sessionStart: number;
+  session: Session;
   demo: Demo;
 }
 /* eslint-enable react/no-unused-prop-types */
 
-function getDemoListEntry(demo: Demo): DemoListEntry {
+function getProtocolSupport(demo: Demo): DemoListEntry["protocolSupport"] {
+  if (demo.isOldProtocol()) {
+    return "older";
+  }
+  return demo.isNewProtocol() ? "newer" : "supported";
+}
+
+function getDemoListEntry(demo: Demo, session: Session): DemoListEntry {
   const { header, events } = demo;
   return {
     filename: demo.getShortName(),
+    game: header.gameDir,
+    supportedGame: demo.isTf2Demo(),
+    demoVersion: header.demoVersion,
+    netVersion: header.netVersion,
+    protocolSupport: getProtocolSupport(demo),
     map: header.mapName,
     playbackTime: header.playbackTime,
+    recording: demo.recording,
     player: header.clientName,
     server: header.serverName,
     numEvents: events.length,
     numTicks: header.numTicks,
     birthtime: demo.birthtime,
     filesize: demo.filesize,
+    sessionStart: session.start,
+    session,
     demo,
   };
 }
 
-function CustomTimeCell({ playbackTime }: DemoListEntry) {
+function CustomFilenameCell({ filename, game, supportedGame }: DemoListEntry) {
+  if (supportedGame) {
+    return <div>{filename}</div>;
+  }
+  return (
+    <div>
+      {filename}
+      <br />
+      <span style={{ color: red[500] }}>Unsupported game ({game})</span>
+    </div>
+  );
+}
+
+function CustomTimeCell({ playbackTime, recording }: DemoListEntry) {
+  if (recording) {
+    return <div>Recording...</div>;
+  }
   return <div>{formatPlaybackTime(playbackTime)}</div>;
 }
 
@@ -80,6 +163,32 @@ function CustomBirthtimeCell({ birthtime }: DemoListEntry) {
   );
 }
 
+function CustomProtocolCell({
+  demoVersion,
+  netVersion,
+  protocolSupport,
+}: DemoListEntry) {
+  const version = `${demoVersion} / ${netVersion}`;
+  if (protocolSupport === "supported") {
+    return <div>{version}</div>;
+  }
+  return (
+    <Tooltip
+      title={
+        protocolSupport === "older"
+          ? "Older than supported, the current game can't play it"
+          : "Newer than supported, some features may not work"
+      }
+    >
+      <div style={{ color: red[500] }}>{version}</div>
+    </Tooltip>
+  );
+}
+
+function CustomSessionCell({ session }: DemoListEntry) {
+  return <div>{getSessionName(session)}</div>;
+}
+
 function CustomFilesizeCell({ filesize }: DemoListEntry) {
   return <div>{formatFileSize(filesize)}</div>;
 }
@@ -89,6 +198,7 @@ const columns = [
     name: "Filename",
     selector: "filename",
     sortable: true,
+    cell: CustomFilenameCell,
     grow: 1.3,
   },
   {
@@ -138,6 +248,21 @@ const columns = [
     grow: 0.1,
     center: true,
   },
+  {
+    name: "Protocol",
+    selector: "netVersion",
+    sortable: true,
+    cell: CustomProtocolCell,
+    grow: 0.1,
+    center: true,
+  },
+  {
+    name: "Session",
+    selector: "sessionStart",
+    sortable: true,
+    cell: CustomSessionCell,
+    grow: 0.5,
+  },
   {
     name: "Size",
     selector: "filesize",
@@ -170,6 +295,7 @@ type DemoTableProps = {
   viewSettings: () => void;
   viewInfoDialog: (info: DemoListInfo) => void;
   viewAutoDeleteDialog: () => void;
+  viewGameDialog: () => void;
 };
 
 type DemoTableState = {
@@ -178,20 +304,60 @@ type DemoTableState = {
   quickFilterQuery: string;
   progressPending: boolean;
   moreMenuAnchor: Element | null;
+  sortField: string;
+  sortAsc: boolean;
+  selectedRows: DemoListEntry[];
+  // Toggled to clear the selection of the table
+  clearSelectedRows: boolean;
+  gameConnected: boolean;
+  // Files that couldn't be read as demos in the last refresh
+  skippedFiles: SkippedFile[];
 };
 
 export default class DemoTable extends PureComponent<
   DemoTableProps,
   DemoTableState
 > {
+  private watcher?: DirectoryWatcher;
+
+  // Names of demos seen while they were being recorded. Only these are
+  // renamed automatically once finished, not demos renamed by the user or
+  // copied into the folder.
+  private unfinishedDemos = new Set<string>();
+
+  private bulkRenameDialog: React.RefObject<BulkRenameDialog>;
+
+  private quarantineDialog: React.RefObject<QuarantineDialog>;
+
+  private rconConsoleDialog: React.RefObject<RconConsoleDialog>;
+
+  private skippedFilesDialog: React.RefObject<SkippedFilesDialog>;
+
+  private backgroundTasksDialog: React.RefObject<BackgroundTasksDialog>;
+
+  private uploadQueueDialog: React.RefObject<UploadQueueDialog>;
+
   constructor(props: DemoTableProps) {
     super(props);
+    this.bulkRenameDialog = React.createRef();
+    this.quarantineDialog = React.createRef();
+    this.rconConsoleDialog = React.createRef();
+    this.skippedFilesDialog = React.createRef();
+    this.backgroundTasksDialog = React.createRef();
+    this.uploadQueueDialog = React.createRef();
+    const preferences = getDirectoryPreferences(cfg.get("demo_path"));
     this.state = {
       data: [],
       filteredData: [],
-      quickFilterQuery: "",
+      quickFilterQuery: preferences.quickFilter,
       progressPending: false,
       moreMenuAnchor: null,
+      sortField: preferences.sortField,
+      sortAsc: preferences.sortAsc,
+      selectedRows: [],
+      clearSelectedRows: false,
+      gameConnected: rconConnection.connected,
+      skippedFiles: [],
     };
   }
 
@@ -199,21 +365,168 @@ export default class DemoTable extends PureComponent<
     if (cfg.has("demo_path")) {
       this.RefreshDemoList();
     }
+    rconConnection.on("connected", this.gameConnectionChanged);
+    rconConnection.on("disconnected", this.gameConnectionChanged);
   }
 
+  componentWillUnmount() {
+    this.watcher?.stop();
+    rconConnection.off("connected", this.gameConnectionChanged);
+    rconConnection.off("disconnected", this.gameConnectionChanged);
+  }
+
+  gameConnectionChanged = () => {
+    this.setState({ gameConnected: rconConnection.connected });
+  };
+
+  // Watches the demo folder, restarting the watcher if the folder changed,
+  // e.g. after it was selected for the first time.
+  updateWatcher = () => {
+    const dirPath: string = cfg.get("demo_path");
+    if (this.watcher?.dirPath === dirPath) {
+      return;
+    }
+    this.watcher?.stop();
+    this.watcher = new DirectoryWatcher(dirPath, this.fileChanged);
+    this.unfinishedDemos.clear();
+    this.watcher.start();
+  };
+
+  // Reloads the events of demos whose events file was changed,
+  // e.g. by a sync tool or another program.
+  fileChanged = (file: string) => {
+    if (file.endsWith(".dem")) {
+      this.demoFileChanged(file);
+      return;
+    }
+    if (!file.endsWith(".json")) {
+      return;
+    }
+    const { data, quickFilterQuery } = this.state;
+    const name = path.basename(file, ".json");
+    const entry = data.find((value) => value.filename === name);
+    if (entry === undefined) {
+      return;
+    }
+    log.debug(`Events file of ${name} changed, reloading events`);
+    const { demo } = entry;
+    demo.events = Demo.readEvents(Demo.getJSONPath(demo.filename));
+    this.setState({
+      data: data.map((value) =>
+        value === entry ? getDemoListEntry(demo, entry.session) : value
+      ),
+    });
+    this.updateQuickFilter(quickFilterQuery);
+  };
+
+  // Renames recordings that were finished while DemoMan is open,
+  // if the user enabled it in the settings.
+  demoFileChanged = async (file: string) => {
+    if (!cfg.get("auto_rename_demos", false)) {
+      return;
+    }
+    const filename = path.join(cfg.get("demo_path"), file);
+    let demo;
+    try {
+      demo = await Demo.create(filename);
+    } catch (e) {
+      // The header of a new recording may not be written completely yet.
+      // Other errors happen when the demo was deleted or renamed meanwhile.
+      if (e instanceof InvalidDemoFileError && fs.existsSync(filename)) {
+        this.unfinishedDemos.add(file);
+      }
+      return;
+    }
+    if (demo.recording || demo.header.numTicks === 0) {
+      this.unfinishedDemos.add(file);
+      return;
+    }
+    if (!this.unfinishedDemos.delete(file)) {
+      return;
+    }
+    try {
+      if (renameDemoByTemplate(demo, getRenameTemplate())) {
+        log.info(`Automatically renamed ${file} to ${demo.getShortName()}`);
+        this.RefreshDemoList();
+      }
+    } catch (e) {
+      log.error(`Error renaming demo ${file}: ${e}`);
+    }
+  };
+
   RefreshDemoList = async () => {
+    this.updateWatcher();
     this.setState({
       data: [],
       filteredData: [],
       progressPending: true,
     });
     const newDemos = await getDemosInDirectory(cfg.get("demo_path"));
-    const newData = newDemos.map(getDemoListEntry);
+    const skippedFiles = getSkippedFiles(cfg.get("demo_path"));
+    newDemos
+      .filter((demo) => demo.recording)
+      .forEach((demo) => {
+        this.unfinishedDemos.add(path.basename(demo.filename));
+      });
+    const sessions = groupIntoSessions(newDemos);
+    const newData = newDemos.map((demo) => {
+      const session = sessions.get(demo) as Session;
+      return getDemoListEntry(demo, session);
+    });
+    // The demo folder may have changed, e.g. when it was just selected
+    const preferences = getDirectoryPreferences(cfg.get("demo_path"));
     this.setState({
       data: newData,
+      skippedFiles,
       progressPending: false,
+      sortField: preferences.sortField,
+      sortAsc: preferences.sortAsc,
     });
-    this.updateQuickFilter("");
+    this.updateQuickFilter(preferences.quickFilter);
+  };
+
+  selectionChanged = (selection: { selectedRows: DemoListEntry[] }) => {
+    this.setState({ selectedRows: selection.selectedRows });
+  };
+
+  bulkRenameDialogOpen = () => {
+    const { selectedRows } = this.state;
+    this.bulkRenameDialog.current?.open(
+      selectedRows
+        .filter((entry) => !entry.recording)
+        .map((entry) => entry.demo)
+    );
+  };
+
+  bulkRenameDialogClose = () => {
+    this.bulkRenameDialog.current?.close();
+  };
+
+  bulkRenameDialogConfirm = (demos: Demo[], template: string) => {
+    cfg.set("rename_template", template);
+    const renamed = renameDemosByTemplate(demos, template);
+    log.info(`Renamed ${renamed} of ${demos.length} demos`);
+    this.bulkRenameDialogClose();
+    this.setState((state) => ({
+      clearSelectedRows: !state.clearSelectedRows,
+    }));
+    this.RefreshDemoList();
+  };
+
+  // Plays the selected demos in the order they were recorded
+  playSelectedDemos = async () => {
+    const { selectedRows } = this.state;
+    const entries = selectedRows
+      .filter((entry) => !entry.recording)
+      .sort((a, b) => a.birthtime - b.birthtime)
+      .map((entry) => ({ demo: entry.demo }));
+    try {
+      await connectToGame();
+      await playPlaylist(entries);
+    } catch (e) {
+      log.error(`Error playing playlist: ${e}`);
+      remote.dialog.showErrorBox("Could not play the demos", e.message);
+    }
   };
 
   viewInfo = () => {
@@ -229,6 +542,108 @@ export default class DemoTable extends PureComponent<
     });
   };
 
+  backupEvents = async () => {
+    const backupPath = remote.dialog.showSaveDialogSync({
+      title: "Back up events",
+      defaultPath: "DemoMan events backup.json",
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    });
+    // This happens when the user cancels the dialog
+    if (backupPath === undefined) {
+      return;
+    }
+    try {
+      await jobManager.run("export", "Backing up events", () =>
+        backupEvents(cfg.get("demo_path"), backupPath)
+      );
+    } catch (e) {
+      log.error(`Error backing up events: ${e}`);
+      remote.dialog.showErrorBox("Could not back up events", e.message);
+    }
+  };
+
+  restoreEvents = async () => {
+    const filePaths = remote.dialog.showOpenDialogSync({
+      title: "Restore events",
+      filters: [{ name: "JSON", extensions: ["json"] }],
+      properties: ["openFile"],
+    });
+    // This happens when the user cancels the dialog
+    if (filePaths === undefined) {
+      return;
+    }
+    try {
+      await restoreEvents(cfg.get("demo_path"), filePaths[0]);
+    } catch (e) {
+      log.error(`Error restoring events: ${e}`);
+      remote.dialog.showErrorBox("Could not restore events", e.message);
+    }
+    this.RefreshDemoList();
+  };
+
+  importLegacyEvents = async () => {
+    const filePaths = remote.dialog.showOpenDialogSync({
+      title: "Import events from the old DemoMan",
+      filters: [{ name: "JSON", extensions: ["json"] }],
+      properties: ["openFile"],
+    });
+    // This happens when the user cancels the dialog
+    if (filePaths === undefined) {
+      return;
+    }
+    try {
+      const imported = await importLegacyEvents(
+        cfg.get("demo_path"),
+        filePaths[0]
+      );
+      log.info(`Imported events of ${imported} demos`);
+    } catch (e) {
+      log.error(`Error importing events: ${e}`);
+      remote.dialog.showErrorBox("Could not import events", e.message);
+    }
+    this.RefreshDemoList();
+  };
+
+  exportClipToolEvents = async () => {
+    const { data } = this.state;
+    const exportPath = remote.dialog.showSaveDialogSync({
+      title: "Export events for clip tools",
+      defaultPath: "_events.txt",
+      filters: [{ name: "Text", extensions: ["txt"] }],
+    });
+    // This happens when the user cancels the dialog
+    if (exportPath === undefined) {
+      return;
+    }
+    const demos = data.map((entry) => entry.demo);
+    try {
+      await jobManager.run("export", "Exporting events for clip tools", () =>
+        fs.promises.writeFile(exportPath, formatEventsForClipTools(demos))
+      );
+    } catch (e) {
+      log.error(`Error exporting events: ${e}`);
+    }
+  };
+
+  saveDiagnosticReport = async () => {
+    const reportPath = remote.dialog.showSaveDialogSync({
+      title: "Save diagnostic report",
+      defaultPath: "DemoMan diagnostics.txt",
+      filters: [{ name: "Text", extensions: ["txt"] }],
+    });
+    // This happens when the user cancels the dialog
+    if (reportPath === undefined) {
+      return;
+    }
+    try {
+      await jobManager.run("export", "Saving diagnostic report", () =>
+        createDiagnosticBundle(reportPath)
+      );
+    } catch (e) {
+      log.error(`Error saving diagnostic report: ${e}`);
+    }
+  };
+
   openMoreMenu = (event: React.MouseEvent<HTMLButtonElement>) => {
     this.setState({ moreMenuAnchor: event.currentTarget });
   };
@@ -241,6 +656,21 @@ export default class DemoTable extends PureComponent<
     e: React.ChangeEvent<HTMLTextAreaElement | HTMLInputElement>
   ) => {
     this.updateQuickFilter(e.target.value);
+    setDirectoryPreferences(cfg.get("demo_path"), {
+      quickFilter: e.target.value,
+    });
+  };
+
+  sortChanged = (
+    column: IDataTableColumn<DemoListEntry>,
+    sortDirection: "asc" | "desc"
+  ) => {
+    if (typeof column.selector === "string") {
+      setDirectoryPreferences(cfg.get("demo_path"), {
+        sortField: column.selector,
+        sortAsc: sortDirection === "asc",
+      });
+    }
   };
 
   updateQuickFilter = (query: string) => {
@@ -268,19 +698,50 @@ export default class DemoTable extends PureComponent<
   };
 
   render() {
-    const { filteredData, quickFilterQuery, progressPending, moreMenuAnchor } =
-      this.state;
-    const { viewDemo, viewSettings, viewAutoDeleteDialog } = this.props;
+    const {
+      filteredData,
+      quickFilterQuery,
+      progressPending,
+      moreMenuAnchor,
+      sortField,
+      sortAsc,
+      clearSelectedRows,
+      gameConnected,
+      skippedFiles,
+    } = this.state;
+    const {
+      viewDemo,
+      viewSettings,
+      viewAutoDeleteDialog,
+      viewGameDialog,
+    } = this.props;
 
     return (
       <>
         <DataTable
+          // The default sort is only applied when the table is created,
+          // so it's recreated when another folder's preferences are loaded
+          key={`${sortField}:${sortAsc}`}
           title="Demos"
           columns={columns}
-          defaultSortField="birthtime"
-          defaultSortAsc={false}
+          defaultSortField={sortField}
+          defaultSortAsc={sortAsc}
+          onSort={this.sortChanged}
           keyField="filename"
           highlightOnHover
+          selectableRows
+          onSelectedRowsChange={this.selectionChanged}
+          clearSelectedRows={clearSelectedRows}
+          contextActions={
+            <>
+              <Button color="inherit" onClick={this.playSelectedDemos}>
+                Play in TF2
+              </Button>
+              <Button color="inherit" onClick={this.bulkRenameDialogOpen}>
+                Rename by template...
+              </Button>
+            </>
+          }
           actions={
             <>
               <Paper
@@ -301,17 +762,44 @@ export default class DemoTable extends PureComponent<
                   <IconButton
                     onClick={() => {
                       this.updateQuickFilter("");
+                      setDirectoryPreferences(cfg.get("demo_path"), {
+                        quickFilter: "",
+                      });
                     }}
                   >
                     <ClearIcon />
                   </IconButton>
                 </Tooltip>
               </Paper>
+              {skippedFiles.length !== 0 && (
+                <Tooltip
+                  title={`${skippedFiles.length} files could not be read`}
+                >
+                  <IconButton
+                    color="secondary"
+                    onClick={() => this.skippedFilesDialog.current?.open()}
+                  >
+                    <WarningIcon />
+                  </IconButton>
+                </Tooltip>
+              )}
               <Tooltip title="Reload demos">
                 <IconButton color="default" onClick={this.RefreshDemoList}>
                   <RefreshIcon />
                 </IconButton>
               </Tooltip>
+              <Tooltip
+                title={
+                  gameConnected ? "TF2 (connected)" : "TF2 (not connected)"
+                }
+              >
+                <IconButton
+                  color={gameConnected ? "primary" : "default"}
+                  onClick={viewGameDialog}
+                >
+                  <VideogameAssetIcon />
+                </IconButton>
+              </Tooltip>
               <Tooltip title="Info">
                 <IconButton color="default" onClick={this.viewInfo}>
                   <InfoIcon />
@@ -368,6 +856,78 @@ export default class DemoTable extends PureComponent<
                 >
                   Convert P-REC bookmarks
                 </MenuItem>
+                <MenuItem
+                  onClick={() => {
+                    this.closeMoreMenu();
+                    this.backupEvents();
+                  }}
+                >
+                  Back up events...
+                </MenuItem>
+                <MenuItem
+                  onClick={() => {
+                    this.closeMoreMenu();
+                    this.restoreEvents();
+                  }}
+                >
+                  Restore events from backup...
+                </MenuItem>
+                <MenuItem
+                  onClick={() => {
+                    this.closeMoreMenu();
+                    this.importLegacyEvents();
+                  }}
+                >
+                  Import events from the old DemoMan...
+                </MenuItem>
+                <MenuItem
+                  onClick={() => {
+                    this.closeMoreMenu();
+                    this.exportClipToolEvents();
+                  }}
+                >
+                  Export events for clip tools...
+                </MenuItem>
+                <MenuItem
+                  onClick={() => {
+                    this.closeMoreMenu();
+                    this.saveDiagnosticReport();
+                  }}
+                >
+                  Save diagnostic report...
+                </MenuItem>
+                <MenuItem
+                  onClick={() => {
+                    this.closeMoreMenu();
+                    this.quarantineDialog.current?.open();
+                  }}
+                >
+                  Quarantined demos...
+                </MenuItem>
+                <MenuItem
+                  onClick={() => {
+                    this.closeMoreMenu();
+                    this.rconConsoleDialog.current?.open();
+                  }}
+                >
+                  TF2 console...
+                </MenuItem>
+                <MenuItem
+                  onClick={() => {
+                    this.closeMoreMenu();
+                    this.backgroundTasksDialog.current?.open();
+                  }}
+                >
+                  Background tasks...
+                </MenuItem>
+                <MenuItem
+                  onClick={() => {
+                    this.closeMoreMenu();
+                    this.uploadQueueDialog.current?.open();
+                  }}
+                >
+                  Uploads...
+                </MenuItem>
               </Menu>
             </>
           }
@@ -380,7 +940,11 @@ export default class DemoTable extends PureComponent<
           sortIcon={<ArrowDownward />}
           pointerOnHover
           onRowClicked={(row: DemoListEntry) => {
-            viewDemo(row.demo);
+            // Renaming or deleting a demo that TF2 is still writing to
+            // would break the recording.
+            if (!row.recording) {
+              viewDemo(row.demo);
+            }
           }}
           fixedHeader
           // 56px is the height of the table title, 57px is the height of the header.
@@ -402,6 +966,22 @@ export default class DemoTable extends PureComponent<
           }
           theme={`${getPreferredTheme()}_alt`}
         />
+        <BulkRenameDialog
+          ref={this.bulkRenameDialog}
+          onClose={this.bulkRenameDialogClose}
+          onConfirm={this.bulkRenameDialogConfirm}
+        />
+        <QuarantineDialog
+          ref={this.quarantineDialog}
+          onChange={this.RefreshDemoList}
+        />
+        <RconConsoleDialog ref={this.rconConsoleDialog} />
+        <SkippedFilesDialog
+          ref={this.skippedFilesDialog}
+          files={skippedFiles}
+        />
+        <BackgroundTasksDialog ref={this.backgroundTasksDialog} />
+        <UploadQueueDialog ref={this.uploadQueueDialog} />
       </>
     );
   }
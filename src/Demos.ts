@@ -5,8 +5,47 @@ import log from "electron-log";
 import StreamReader from "./StreamReader";
 import { DemoHeader, InvalidDemoFileError } from "./DemoHeader";
 import DemoEvent from "./DemoEvent";
+import {
+  mapSettledConcurrently,
+  normalizeDirPath,
+  normalizeFilePath,
+} from "./util";
+import HeaderCache from "./HeaderCache";
+import { filterQuarantined, recordScanResults } from "./Quarantine";
+import jobManager, { JobContext } from "./Jobs";
 
-const HEADER_SIZE = 8 + 4 + 4 + 260 + 260 + 260 + 260 + 4 + 4 + 4 + 4;
+export const HEADER_SIZE = 8 + 4 + 4 + 260 + 260 + 260 + 260 + 4 + 4 + 4 + 4;
+
+// Used when the header doesn't contain enough information
+// to calculate the tick interval, e.g. for crashed recordings.
+const DEFAULT_TICK_INTERVAL = 0.015;
+
+// Protocol versions of demos recorded with current TF2 builds. The game
+// can't play demos with an older network protocol.
+const CURRENT_DEMO_PROTOCOL = 3;
+const CURRENT_NETWORK_PROTOCOL = 24;
+
+// Limits the number of files opened at once while scanning
+const MAX_CONCURRENT_READS = 64;
+
+// TF2 only writes the tick count to the header once a recording is
+// stopped. Demos without one that were modified more recently than this
+// are assumed to still be recording.
+const RECORDING_TIMEOUT_MS = 30000;
+
+const scansInProgress = new Map<string, Promise<Demo[]>>();
+
+export interface SkippedFile {
+  filename: string;
+  reason: string;
+}
+
+// Files that couldn't be read in the last scan of each directory
+const skippedFiles = new Map<string, SkippedFile[]>();
+
+function getErrorMessage(e: unknown) {
+  return e instanceof Error ? e.message : String(e);
+}
 
 export function writeEventsFile(
   events: DemoEvent[],
@@ -44,50 +83,75 @@ export class Demo {
 
   events: DemoEvent[];
 
+  recording: boolean;
+
   private constructor(
     filename: string,
     header: DemoHeader,
     events: DemoEvent[],
     birthtime: number,
-    filesize: number
+    filesize: number,
+    recording: boolean
   ) {
     this.filename = filename;
     this.header = header;
     this.events = events;
     this.birthtime = birthtime;
     this.filesize = filesize;
+    this.recording = recording;
   }
 
-  static create(filename: string): Demo {
-    const stats = fs.statSync(filename);
+  static async create(
+    filename: string,
+    headerCache?: HeaderCache
+  ): Promise<Demo> {
+    const stats = await fs.promises.stat(filename);
+    let header = headerCache?.get(filename, stats);
+    if (header === undefined) {
+      header = await this.readFileHeader(filename);
+    }
+    const recording =
+      header.numTicks === 0 &&
+      Date.now() - stats.mtimeMs < RECORDING_TIMEOUT_MS;
+    if (recording) {
+      log.debug(`Demo ${filename} is still being recorded`);
+    } else {
+      headerCache?.set(filename, stats, header);
+    }
     return new Demo(
       filename,
-      this.readFileHeader(filename),
+      header,
       this.readEvents(this.getJSONPath(filename)),
       stats.birthtimeMs,
-      stats.size
+      stats.size,
+      recording
     );
   }
 
-  static readFileHeader(filename: string): DemoHeader {
+  static async readFileHeader(filename: string): Promise<DemoHeader> {
     log.debug(`Reading file header of ${filename}`);
-    const fd = fs.openSync(filename, "r");
     const buf = Buffer.allocUnsafe(HEADER_SIZE);
-
-    const bytesRead = fs.readSync(fd, buf, 0, HEADER_SIZE, 0);
+    const file = await fs.promises.open(filename, "r");
+    let bytesRead = 0;
+    try {
+      ({ bytesRead } = await file.read(buf, 0, HEADER_SIZE, 0));
+    } finally {
+      await file.close();
+    }
     if (bytesRead !== HEADER_SIZE) {
       log.warn(
         `Error reading file ${filename}: read ${bytesRead} bytes, expected ${HEADER_SIZE}.`
       );
-      throw new InvalidDemoFileError();
+      throw new InvalidDemoFileError(
+        `file is too short (${bytesRead} bytes) to contain a demo header`
+      );
     }
-    fs.closeSync(fd);
     const sr = new StreamReader(buf);
 
     const filestamp = sr.readString(8);
     if (filestamp !== "HL2DEMO") {
       log.warn(`File ${filename} has an invalid file stamp '${filestamp}'!`);
-      throw new InvalidDemoFileError();
+      throw new InvalidDemoFileError(`invalid file stamp '${filestamp}'`);
     }
 
     const header: DemoHeader = {
@@ -106,13 +170,53 @@ export class Demo {
   }
 
   static getJSONPath(filename: string) {
-    return filename.replace(/\.dem$/gi, ".json");
+    return normalizeFilePath(filename).replace(/\.dem$/i, ".json");
   }
 
   getShortName() {
     return path.basename(this.filename, ".dem");
   }
 
+  // Other Source games use the same demo format, but a different game dir
+  isTf2Demo() {
+    return this.header.gameDir.toLowerCase() === "tf";
+  }
+
+  isOldProtocol() {
+    return (
+      this.header.demoVersion < CURRENT_DEMO_PROTOCOL ||
+      this.header.netVersion < CURRENT_NETWORK_PROTOCOL
+    );
+  }
+
+  // Demos from game updates newer than DemoMan may not be read correctly
+  isNewProtocol() {
+    return (
+      !this.isOldProtocol() &&
+      (this.header.demoVersion > CURRENT_DEMO_PROTOCOL ||
+        this.header.netVersion > CURRENT_NETWORK_PROTOCOL)
+    );
+  }
+
+  getTickInterval() {
+    const { playbackTime, numTicks } = this.header;
+    if (numTicks <= 0 || playbackTime <= 0) {
+      return DEFAULT_TICK_INTERVAL;
+    }
+    return playbackTime / numTicks;
+  }
+
+  // Returns the time since the start of the demo at the given tick, in seconds
+  tickToSeconds(tick: number) {
+    return tick * this.getTickInterval();
+  }
+
+  // Estimates the wall clock time at the given tick, as a timestamp.
+  // The file's creation time is used as the start of the recording.
+  tickToWallClock(tick: number) {
+    return this.birthtime + this.tickToSeconds(tick) * 1000;
+  }
+
   static readEvents(jsonPath: string): DemoEvent[] {
     log.debug(`Looking for events file at ${jsonPath}`);
     let content;
@@ -140,18 +244,21 @@ export class Demo {
 
   rename(newName: string) {
     log.info(`Renaming demo ${this.getShortName()} to ${newName}`);
-    const dir = path.dirname(this.filename);
+    const filename = normalizeFilePath(this.filename);
+    const dir = path.dirname(filename);
     const newNameFull = path.join(dir, `${newName}.dem`);
-    fs.renameSync(this.filename, newNameFull);
+    fs.renameSync(filename, newNameFull);
     try {
       fs.renameSync(
-        Demo.getJSONPath(this.filename),
+        Demo.getJSONPath(filename),
         path.join(dir, `${newName}.json`)
       );
     } catch (e) {
       if (e.code === "ENOENT") {
         // This demo has no events file, ignore the error
       } else {
+        // Keep the demo and its events together
+        fs.renameSync(newNameFull, filename);
         throw e;
       }
     }
@@ -160,9 +267,10 @@ export class Demo {
 
   delete() {
     log.info(`Deleting demo ${this.filename}`);
-    fs.rmSync(this.filename);
+    const filename = normalizeFilePath(this.filename);
+    fs.rmSync(filename);
     try {
-      fs.rmSync(Demo.getJSONPath(this.filename));
+      fs.rmSync(Demo.getJSONPath(filename));
     } catch (e) {
       if (e.code === "ENOENT") {
         // This demo has no events file, ignore the error
@@ -173,7 +281,7 @@ export class Demo {
   }
 }
 
-export async function getDemosInDirectory(dirPath: string) {
+async function scanDirectory(dirPath: string, job: JobContext) {
   log.debug(`Finding demo files in ${dirPath}`);
 
   let files;
@@ -181,21 +289,113 @@ export async function getDemosInDirectory(dirPath: string) {
     files = await fs.promises.readdir(dirPath);
   } catch (e) {
     log.error(`Error reading path ${dirPath}: ${e}`);
+    skippedFiles.delete(normalizeDirPath(dirPath));
     return [];
   }
 
-  const demoList: Demo[] = [];
-  files.forEach((file) => {
+  const demoFiles = files.filter((file) => {
     if (file.endsWith(".dem")) {
       log.debug(`Found demo file ${file}`);
-      try {
-        demoList.push(Demo.create(path.join(dirPath, file)));
-      } catch (error) {
-        // ignore this file if it throws errors
-      }
+      return true;
+    }
+    log.debug(`Found non-demo file ${file}, skipping.`);
+    return false;
+  });
+
+  // Symlinks can make the same demo appear under multiple names,
+  // only the first one pointing to each file is listed.
+  const realPaths = await mapSettledConcurrently(
+    demoFiles,
+    MAX_CONCURRENT_READS,
+    (file) => fs.promises.realpath(path.join(dirPath, file))
+  );
+  const seenPaths = new Set<string>();
+  const skipped: SkippedFile[] = [];
+  const uniqueFiles = demoFiles.filter((file, i) => {
+    const result = realPaths[i];
+    if (result.status === "rejected") {
+      log.debug(`Error resolving ${file}: ${result.reason}`);
+      skipped.push({
+        filename: path.join(dirPath, file),
+        reason: getErrorMessage(result.reason),
+      });
+      return false;
+    }
+    if (seenPaths.has(result.value)) {
+      log.debug(`Skipping ${file}, it links to ${result.value}`);
+      return false;
+    }
+    seenPaths.add(result.value);
+    return true;
+  });
+
+  const filenames = await filterQuarantined(
+    uniqueFiles.map((file) => path.join(dirPath, file))
+  );
+
+  const headerCache = await HeaderCache.load(dirPath);
+  // Only the headers are read, so demos can be loaded concurrently.
+  let loaded = 0;
+  const results = await mapSettledConcurrently(
+    filenames,
+    MAX_CONCURRENT_READS,
+    (filename) =>
+      Demo.create(filename, headerCache).finally(() => {
+        loaded += 1;
+        job.setProgress(loaded / filenames.length);
+      })
+  );
+  try {
+    await headerCache.save();
+  } catch (e) {
+    log.warn(`Error saving header cache for ${dirPath}: ${e}`);
+  }
+  await recordScanResults(
+    results.map((result, i) => [
+      filenames[i],
+      result.status === "rejected" ? result.reason : undefined,
+    ])
+  );
+
+  const demoList: Demo[] = [];
+  results.forEach((result, i) => {
+    if (result.status === "fulfilled") {
+      demoList.push(result.value);
     } else {
-      log.debug(`Found non-demo file ${file}, skipping.`);
+      skipped.push({
+        filename: filenames[i],
+        reason: getErrorMessage(result.reason),
+      });
     }
   });
+  skippedFiles.set(normalizeDirPath(dirPath), skipped);
   return demoList;
 }
+
+/**
+ * Returns the files that couldn't be read in the last scan of the
+ * directory and why. Quarantined files aren't included.
+ */
+export function getSkippedFiles(dirPath: string) {
+  return skippedFiles.get(normalizeDirPath(dirPath)) ?? [];
+}
+
+/**
+ * Returns the demos in the directory. If a scan of the same directory is
+ * already running, its result is shared instead of scanning twice.
+ */
+export function getDemosInDirectory(dirPath: string) {
+  const key = normalizeDirPath(dirPath);
+  let scan = scansInProgress.get(key);
+  if (scan === undefined) {
+    scan = jobManager
+      .run("scan", `Scanning ${dirPath}`, (job) => scanDirectory(dirPath, job))
+      .finally(() => {
+        scansInProgress.delete(key);
+      });
+    scansInProgress.set(key, scan);
+  } else {
+    log.debug(`Scan of ${dirPath} already in progress`);
+  }
+  return scan;
+}
@@ -0,0 +1,135 @@
+import crypto from "crypto";
+import fs from "fs";
+import https from "https";
+import path from "path";
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import { Demo } from "./Demos";
+import { JobCancelledError, JobContext } from "./Jobs";
+
+const UPLOAD_URL = "https://api.demos.tf/upload";
+
+function buildFormField(boundary: string, name: string, value: string) {
+  return (
+    `--${boundary}\r\n` +
+    `Content-Disposition: form-data; name="${name}"\r\n\r\n` +
+    `${value}\r\n`
+  );
+}
+
+export function getDemosTfUrl(demo: Demo): string | undefined {
+  return cfg.get("demostf_urls", {})[demo.filename];
+}
+
+/**
+ * Uploads a demo to demos.tf and returns the URL of the uploaded demo.
+ * The URL is remembered and can be retrieved with getDemosTfUrl.
+ * If maxBytesPerSecond is given, the upload is slowed down to that rate.
+ * The upload reports its progress to the job and can be cancelled.
+ */
+export async function uploadToDemosTf(
+  demo: Demo,
+  apiKey: string,
+  job?: JobContext,
+  maxBytesPerSecond?: number
+) {
+  // The demo may have changed since it was listed, e.g. after a repair,
+  // and Content-Length has to match the data that is actually sent
+  const { size } = await fs.promises.stat(demo.filename);
+  if (size === 0) {
+    throw new Error(`${demo.filename} is empty`);
+  }
+  const filename = path.basename(demo.filename);
+  const boundary = `----DemoMan${crypto.randomBytes(16).toString("hex")}`;
+  const head = Buffer.from(
+    buildFormField(boundary, "key", apiKey) +
+      buildFormField(boundary, "name", filename) +
+      buildFormField(boundary, "red", "RED") +
+      buildFormField(boundary, "blu", "BLU") +
+      `--${boundary}\r\n` +
+      `Content-Disposition: form-data; name="demo"; filename="${filename}"\r\n` +
+      "Content-Type: application/octet-stream\r\n\r\n"
+  );
+  const tail = Buffer.from(`\r\n--${boundary}--\r\n`);
+  const total = head.length + size + tail.length;
+
+  log.info(`Uploading ${demo.filename} to demos.tf`);
+  return new Promise<string>((resolve, reject) => {
+    const request = https.request(
+      UPLOAD_URL,
+      {
+        method: "POST",
+        headers: {
+          "Content-Type": `multipart/form-data; boundary=${boundary}`,
+          "Content-Length": total,
+        },
+      },
+      (response) => {
+        let body = "";
+        response.on("data", (chunk) => {
+          body += chunk;
+        });
+        response.on("end", () => {
+          // The response is a message like "STV available at: <url>"
+          const url = /https?:\/\/\S+/.exec(body);
+          if (response.statusCode !== 200 || url === null) {
+            log.warn(`demos.tf upload failed: ${body}`);
+            reject(new Error(body));
+            return;
+          }
+          log.info(`Uploaded ${demo.filename} to ${url[0]}`);
+          cfg.set("demostf_urls", {
+            ...cfg.get("demostf_urls", {}),
+            [demo.filename]: url[0],
+          });
+          resolve(url[0]);
+        });
+      }
+    );
+    request.on("error", reject);
+
+    let bytesSent = 0;
+    const send = (chunk: Buffer) => {
+      bytesSent += chunk.length;
+      job?.setProgress(bytesSent / total);
+      return request.write(chunk);
+    };
+    send(head);
+    const startTime = Date.now();
+    // Data appended after the stat isn't sent
+    const stream = fs.createReadStream(demo.filename, { end: size - 1 });
+    stream.on("data", (chunk: Buffer) => {
+      const waits: Promise<unknown>[] = [];
+      if (!send(chunk)) {
+        waits.push(new Promise((resume) => request.once("drain", resume)));
+      }
+      if (maxBytesPerSecond !== undefined) {
+        // Waits until the average rate is back at the limit
+        const delay =
+          (bytesSent / maxBytesPerSecond) * 1000 - (Date.now() - startTime);
+        if (delay > 0) {
+          waits.push(new Promise((resume) => setTimeout(resume, delay)));
+        }
+      }
+      if (waits.length !== 0) {
+        stream.pause();
+        Promise.all(waits).then(() => stream.resume());
+      }
+    });
+    stream.on("error", (e) => {
+      request.destroy();
+      reject(e);
+    });
+    stream.on("end", () => {
+      send(tail);
+      request.end();
+    });
+    job?.onCancel(() => {
+      log.info(`Cancelled upload of ${demo.filename}`);
+      stream.destroy();
+      request.destroy();
+      reject(new JobCancelledError());
+    });
+  });
+}
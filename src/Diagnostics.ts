@@ -0,0 +1,57 @@
+import fs from "fs";
+import os from "os";
+import { remote } from "electron";
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+// Settings that must not end up in bug reports
+const SECRET_SETTINGS = [
+  // Credentials stored here when the OS keyring isn't available
+  "credentials",
+  "steam_api_key",
+  "gist_token",
+  "rcon_servers",
+  "demostf_api_key",
+  // Console commands can contain passwords, e.g. rcon_password
+  "rcon_history",
+];
+
+export async function getRecentLogs(lines = 200) {
+  const logPath = log.transports.file.getFile().path;
+  let content;
+  try {
+    content = (await fs.promises.readFile(logPath)).toString();
+  } catch (e) {
+    if (e.code === "ENOENT") {
+      return "";
+    }
+    throw e;
+  }
+  return content.split("\n").slice(-lines).join("\n");
+}
+
+/**
+ * Writes a report with version info, settings and recent log lines,
+ * meant to be attached to GitHub issues.
+ */
+export async function createDiagnosticBundle(bundlePath: string) {
+  log.info(`Writing diagnostic report to ${bundlePath}`);
+  const settings = { ...cfg.getAll() };
+  SECRET_SETTINGS.forEach((key) => {
+    if (key in settings) {
+      settings[key] = "<redacted>";
+    }
+  });
+  const report = [
+    `DemoMan ${remote.app.getVersion()}`,
+    `Electron ${process.versions.electron}`,
+    `${os.type()} ${os.release()} (${process.arch})`,
+    "",
+    "Settings:",
+    JSON.stringify(settings, null, "\t"),
+    "",
+    "Recent log:",
+    await getRecentLogs(),
+  ];
+  await fs.promises.writeFile(bundlePath, report.join("\n"));
+}
@@ -0,0 +1,52 @@
+import cfg from "electron-cfg";
+
+import { normalizeDirPath } from "./util";
+
+export interface DirectoryPreferences {
+  sortField: string;
+  sortAsc: boolean;
+  quickFilter: string;
+}
+
+const defaultPreferences: DirectoryPreferences = {
+  sortField: "birthtime",
+  sortAsc: false,
+  quickFilter: "",
+};
+
+// Preferences are stored in a single object keyed by directory,
+// since electron-cfg would interpret dots in paths as nested keys.
+const CFG_KEY = "directory_preferences";
+
+// Preferences stored before paths were normalized are keyed by the path
+// as it was entered, so that key is checked as well.
+function getStoredPreferences(dirPath: string) {
+  const allPreferences = cfg.get(CFG_KEY, {});
+  return allPreferences[normalizeDirPath(dirPath)] ?? allPreferences[dirPath];
+}
+
+export function getDirectoryPreferences(
+  dirPath: string | undefined
+): DirectoryPreferences {
+  if (dirPath === undefined) {
+    return defaultPreferences;
+  }
+  return { ...defaultPreferences, ...getStoredPreferences(dirPath) };
+}
+
+export function setDirectoryPreferences(
+  dirPath: string | undefined,
+  preferences: Partial<DirectoryPreferences>
+) {
+  if (dirPath === undefined) {
+    return;
+  }
+  const allPreferences = cfg.get(CFG_KEY, {});
+  cfg.set(CFG_KEY, {
+    ...allPreferences,
+    [normalizeDirPath(dirPath)]: {
+      ...getDirectoryPreferences(dirPath),
+      ...preferences,
+    },
+  });
+}
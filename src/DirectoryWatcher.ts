@@ -0,0 +1,63 @@
+import fs from "fs";
+import log from "electron-log";
+
+// File system events often come in bursts, e.g. while a file is written
+const DEBOUNCE_DELAY = 500;
+
+export type FileChangedCallback = (filename: string) => void;
+
+/**
+ * Calls the callback with the name of each file in the directory
+ * that was created, changed or removed, once it has settled.
+ */
+export default class DirectoryWatcher {
+  dirPath: string;
+
+  onFileChanged: FileChangedCallback;
+
+  watcher?: fs.FSWatcher;
+
+  timers: Map<string, NodeJS.Timeout> = new Map();
+
+  constructor(dirPath: string, onFileChanged: FileChangedCallback) {
+    this.dirPath = dirPath;
+    this.onFileChanged = onFileChanged;
+  }
+
+  start() {
+    log.debug(`Watching ${this.dirPath} for changes`);
+    try {
+      this.watcher = fs.watch(this.dirPath, this.handleEvent);
+    } catch (e) {
+      log.error(`Error watching ${this.dirPath}: ${e}`);
+      return;
+    }
+    this.watcher.on("error", (e) => {
+      log.error(`Error watching ${this.dirPath}: ${e}`);
+    });
+  }
+
+  stop() {
+    this.watcher?.close();
+    this.watcher = undefined;
+    this.timers.forEach((timer) => clearTimeout(timer));
+    this.timers.clear();
+  }
+
+  handleEvent = (_eventType: string, filename: string | null) => {
+    if (!filename) {
+      return;
+    }
+    const timer = this.timers.get(filename);
+    if (timer !== undefined) {
+      clearTimeout(timer);
+    }
+    this.timers.set(
+      filename,
+      setTimeout(() => {
+        this.timers.delete(filename);
+        this.onFileChanged(filename);
+      }, DEBOUNCE_DELAY)
+    );
+  };
+}
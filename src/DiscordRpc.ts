@@ -0,0 +1,175 @@
+import crypto from "crypto";
+import net from "net";
+import os from "os";
+import path from "path";
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+// See https://discord.com/developers/docs/topics/rpc
+
+enum DiscordOpcode {
+  HANDSHAKE = 0,
+  FRAME = 1,
+  CLOSE = 2,
+}
+
+// Discord listens on the first free one of these pipes
+const MAX_IPC_ID = 10;
+
+let socket: net.Socket | null = null;
+
+// Only warn once per session, presence is updated on every view change
+let warnedMissingClientId = false;
+
+function getIpcPath(id: number) {
+  if (process.platform === "win32") {
+    return `\\\\?\\pipe\\discord-ipc-${id}`;
+  }
+  const { XDG_RUNTIME_DIR, TMPDIR } = process.env;
+  return path.join(
+    XDG_RUNTIME_DIR || TMPDIR || os.tmpdir(),
+    `discord-ipc-${id}`
+  );
+}
+
+function encode(op: DiscordOpcode, payload: unknown) {
+  const data = Buffer.from(JSON.stringify(payload));
+  const header = Buffer.alloc(8);
+  header.writeInt32LE(op, 0);
+  header.writeInt32LE(data.length, 4);
+  return Buffer.concat([header, data]);
+}
+
+function tryConnect(id: number, clientId: string) {
+  return new Promise<net.Socket>((resolve, reject) => {
+    const newSocket = net.createConnection(getIpcPath(id), () => {
+      newSocket.write(
+        encode(DiscordOpcode.HANDSHAKE, { v: 1, client_id: clientId })
+      );
+    });
+    const fail = (e: Error) => {
+      newSocket.removeAllListeners("data");
+      newSocket.destroy();
+      reject(e);
+    };
+    // Discord answers the handshake with a READY event, or closes the
+    // connection with a reason, e.g. for an invalid application ID
+    let received = Buffer.alloc(0);
+    const onData = (data: Buffer) => {
+      received = Buffer.concat([received, data]);
+      if (received.length < 8) {
+        return;
+      }
+      const op = received.readInt32LE(0);
+      const length = received.readInt32LE(4);
+      if (received.length < 8 + length) {
+        return;
+      }
+      newSocket.removeListener("data", onData);
+      let payload;
+      try {
+        payload = JSON.parse(received.toString("utf8", 8, 8 + length));
+      } catch (e) {
+        fail(e);
+        return;
+      }
+      if (op === DiscordOpcode.FRAME && payload.evt === "READY") {
+        resolve(newSocket);
+      } else if (op === DiscordOpcode.CLOSE) {
+        fail(new Error(`Discord closed the connection: ${payload.message}`));
+      } else {
+        fail(new Error(`Unexpected handshake response: ${op}`));
+      }
+    };
+    newSocket.on("data", onData);
+    newSocket.once("error", fail);
+  });
+}
+
+async function connect(clientId: string) {
+  for (let id = 0; id < MAX_IPC_ID; id += 1) {
+    try {
+      // eslint-disable-next-line no-await-in-loop
+      const newSocket = await tryConnect(id, clientId);
+      log.debug(`[Discord]\tConnected to discord-ipc-${id}`);
+      newSocket.on("close", () => {
+        if (socket === newSocket) {
+          socket = null;
+        }
+      });
+      newSocket.on("error", (e) => {
+        log.debug(`[Discord]\tSocket error: ${e}`);
+      });
+      return newSocket;
+    } catch (e) {
+      // A pipe that answered belongs to Discord, so the others won't work
+      // either
+      if (e.code === undefined) {
+        throw e;
+      }
+      // Try the next pipe
+    }
+  }
+  throw new Error("Discord is not running");
+}
+
+function encodeActivity(activity?: Record<string, unknown>) {
+  return encode(DiscordOpcode.FRAME, {
+    cmd: "SET_ACTIVITY",
+    args: { pid: process.pid, activity },
+    nonce: crypto.randomBytes(16).toString("hex"),
+  });
+}
+
+/**
+ * Removes the activity from the user's Discord profile and closes the
+ * connection. Called when Rich Presence is turned off. Resolves once the
+ * request was sent.
+ */
+export function clearPresence() {
+  const currentSocket = socket;
+  if (currentSocket === null) {
+    return Promise.resolve();
+  }
+  log.debug("[Discord]\tClearing activity");
+  socket = null;
+  return new Promise<void>((resolve) => {
+    // end() sends the pending data before closing the connection
+    currentSocket.end(encodeActivity(), () => resolve());
+  });
+}
+
+async function setActivity(activity: Record<string, unknown>) {
+  const clientId: string = cfg.get("discord_client_id", "");
+  if (!cfg.get("discord_rich_presence", false)) {
+    await clearPresence();
+    return;
+  }
+  if (clientId === "") {
+    if (!warnedMissingClientId) {
+      log.warn("[Discord]\tNo application ID set for Rich Presence");
+      warnedMissingClientId = true;
+    }
+    return;
+  }
+  try {
+    if (socket === null) {
+      socket = await connect(clientId);
+    }
+    socket.write(encodeActivity(activity));
+  } catch (e) {
+    log.debug(`[Discord]\tCould not set activity: ${e}`);
+  }
+}
+
+/**
+ * Shows the given activity in the user's Discord profile, if enabled
+ * in the settings. Errors are ignored, as the presence is purely cosmetic.
+ */
+export default function setPresence(details: string, state?: string) {
+  return setActivity({
+    details,
+    state,
+    timestamps: { start: Date.now() },
+  });
+}
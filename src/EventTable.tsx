@@ -4,10 +4,13 @@ import DataTable from "react-data-table-component";
 import ArrowDownward from "@material-ui/icons/ArrowDownward";
 import IconButton from "@material-ui/core/IconButton";
 import AddIcon from "@material-ui/icons/Add";
+import PlayArrowIcon from "@material-ui/icons/PlayArrow";
 import Tooltip from "@material-ui/core/Tooltip";
 
+import { Demo } from "./Demos";
 import { getPreferredTheme } from "./theme";
 import EventTableEntry from "./EventTableEntry";
+import { formatPlaybackTime } from "./util";
 
 const columns = [
   {
@@ -31,20 +34,60 @@ const columns = [
 ];
 
 type EventTableProps = {
+  demo: Demo;
   data: EventTableEntry[];
   editEvent: (event: EventTableEntry) => void;
   addEvent: () => void;
+  // Only set while the demo can be played back in the game
+  skipToEvent?: (event: EventTableEntry) => void;
+  // Events up to this tick are highlighted while the demo plays in the game
+  playbackTick: number | null;
 };
 
 export default function EventTable(props: EventTableProps) {
-  const { data, editEvent, addEvent } = props;
+  const { demo, data, editEvent, addEvent, skipToEvent, playbackTick } = props;
+  // The time of day is estimated from the file's creation time
+  const timeColumn = {
+    name: "Time",
+    selector: "event.tick",
+    sortable: true,
+    cell: ({ event }: EventTableEntry) => (
+      <div style={{ whiteSpace: "nowrap" }}>
+        {formatPlaybackTime(demo.tickToSeconds(event.tick))}
+        <br />
+        {new Date(demo.tickToWallClock(event.tick)).toLocaleTimeString()}
+      </div>
+    ),
+    grow: 1,
+  };
+  const eventColumns = [columns[0], timeColumn, ...columns.slice(1)];
+  const skipColumn = skipToEvent && {
+    name: "",
+    cell: (row: EventTableEntry) => (
+      <Tooltip title="Skip playback to this event">
+        <IconButton size="small" onClick={() => skipToEvent(row)}>
+          <PlayArrowIcon />
+        </IconButton>
+      </Tooltip>
+    ),
+    ignoreRowClick: true,
+    button: true,
+    width: "56px",
+  };
   return (
     <DataTable
       title="Events"
-      columns={columns}
+      columns={skipColumn ? [skipColumn, ...eventColumns] : eventColumns}
       defaultSortField="event.tick"
       defaultSortAsc
       highlightOnHover
+      conditionalRowStyles={[
+        {
+          when: (row: EventTableEntry) =>
+            playbackTick !== null && row.event.tick <= playbackTick,
+          style: { backgroundColor: "rgba(33, 150, 243, 0.25)" },
+        },
+      ]}
       noDataComponent={
         <div style={{ height: "2rem" }}>
           This demo doesn&apos;t have any events recorded.
@@ -0,0 +1,126 @@
+import fs from "fs";
+import path from "path";
+import log from "electron-log";
+
+import DemoEvent from "./DemoEvent";
+import { Demo, writeEventsFile } from "./Demos";
+
+const BACKUP_VERSION = 1;
+
+interface EventsBackup {
+  version: number;
+  demos: Record<string, DemoEvent[]>;
+}
+
+// Demo names come from the backup file, so they must not be able to point
+// outside of the demo folder
+function isValidDemoName(demoName: string) {
+  return !/[\\/]/.test(demoName) && !demoName.includes("..");
+}
+
+function isSameEvent(a: DemoEvent, b: DemoEvent) {
+  return a.tick === b.tick && a.name === b.name && a.value === b.value;
+}
+
+export async function backupEvents(demoDir: string, backupPath: string) {
+  log.info(`Backing up events in ${demoDir} to ${backupPath}`);
+  const files = await fs.promises.readdir(demoDir);
+  const backup: EventsBackup = { version: BACKUP_VERSION, demos: {} };
+
+  files.forEach((file) => {
+    if (file.endsWith(".dem")) {
+      const events = Demo.readEvents(
+        Demo.getJSONPath(path.join(demoDir, file))
+      );
+      if (events.length !== 0) {
+        backup.demos[path.basename(file, ".dem")] = events;
+      }
+    }
+  });
+  await fs.promises.writeFile(backupPath, JSON.stringify(backup, null, "\t"));
+  return Object.keys(backup.demos).length;
+}
+
+// Merges the events into the events files of the demos, which are given by
+// name without extension. Returns the number of demos whose events changed.
+function mergeEvents(demoDir: string, demos: Record<string, DemoEvent[]>) {
+  let merged = 0;
+  Object.keys(demos).forEach((demoName) => {
+    if (!isValidDemoName(demoName)) {
+      log.warn(`Skipping events for invalid demo name ${demoName}`);
+      return;
+    }
+    const demoPath = path.join(demoDir, `${demoName}.dem`);
+    // only restore events for demos that still exist
+    if (!fs.existsSync(demoPath)) {
+      log.debug(`Skipping events for missing demo ${demoName}`);
+      return;
+    }
+    const jsonPath = Demo.getJSONPath(demoPath);
+    const events = Demo.readEvents(jsonPath);
+    demos[demoName].forEach((event) => {
+      if (!events.some((existing) => isSameEvent(existing, event))) {
+        events.push(event);
+      }
+    });
+    writeEventsFile(events, jsonPath, true);
+    merged += 1;
+  });
+  return merged;
+}
+
+/**
+ * Restores events from a backup file. Events are merged with the ones
+ * already present, so restoring the same backup twice is harmless.
+ * Returns the number of demos whose events were restored.
+ */
+export async function restoreEvents(demoDir: string, backupPath: string) {
+  log.info(`Restoring events in ${demoDir} from ${backupPath}`);
+  const content = await fs.promises.readFile(backupPath);
+  const backup: EventsBackup = JSON.parse(content.toString());
+  if (backup.version !== BACKUP_VERSION || backup.demos === undefined) {
+    throw new Error(`Unsupported backup file ${backupPath}`);
+  }
+  return mergeEvents(demoDir, backup.demos);
+}
+
+function isDemoEvent(event: DemoEvent) {
+  return (
+    typeof event === "object" &&
+    event !== null &&
+    typeof event.name === "string" &&
+    typeof event.value === "string" &&
+    Number.isInteger(event.tick)
+  );
+}
+
+/**
+ * Imports the events saved by the Python version of DemoMan. It kept the
+ * events of all demos in a single JSON file, mapping the demo file names
+ * to either a list of events or an object in the format of the events
+ * files. Like restoring a backup, the events are merged with the existing
+ * ones. Returns the number of demos whose events were imported.
+ */
+export async function importLegacyEvents(demoDir: string, filePath: string) {
+  log.info(`Importing events in ${demoDir} from ${filePath}`);
+  const content = await fs.promises.readFile(filePath);
+  const legacy = JSON.parse(content.toString());
+  if (typeof legacy !== "object" || legacy === null) {
+    throw new Error(`Unsupported events file ${filePath}`);
+  }
+  const demos: Record<string, DemoEvent[]> = {};
+  Object.entries(legacy).forEach(([demoFile, value]) => {
+    const events = Array.isArray(value)
+      ? value
+      : (value as { events?: unknown })?.events;
+    if (!Array.isArray(events)) {
+      log.debug(`Skipping ${demoFile}, it has no list of events`);
+      return;
+    }
+    demos[demoFile.replace(/\.dem$/i, "")] = events.filter(isDemoEvent);
+  });
+  if (Object.keys(demos).length === 0) {
+    throw new Error(`${filePath} doesn't contain any events`);
+  }
+  return mergeEvents(demoDir, demos);
+}
@@ -0,0 +1,153 @@
+import DemoEvent from "./DemoEvent";
+import { Demo } from "./Demos";
+import { formatPlaybackTime } from "./util";
+import { formatVdm, VdmAction } from "./Vdm";
+
+// Time included before and after each event when exporting clips, in seconds
+const CLIP_PADDING_BEFORE = 10;
+const CLIP_PADDING_AFTER = 5;
+
+export interface TickRange {
+  start: number;
+  end: number;
+}
+
+function getSortedEvents(demo: Demo): DemoEvent[] {
+  return [...demo.events].sort((a, b) => a.tick - b.tick);
+}
+
+export function formatEventsAsText(demo: Demo, markdown = true) {
+  const { header } = demo;
+  const duration = formatPlaybackTime(header.playbackTime);
+  const title = `${demo.getShortName()} (${header.mapName}, ${duration})`;
+  const lines = [markdown ? `**${title}**` : title];
+
+  getSortedEvents(demo).forEach((event) => {
+    const time = formatPlaybackTime(demo.tickToSeconds(event.tick));
+    lines.push(
+      `${markdown ? `\`${time}\`` : time} ${event.name}: ${event.value}`
+    );
+  });
+  return lines.join("\n");
+}
+
+/**
+ * Returns the tick ranges around the demo's events, merging ranges that
+ * overlap so close events end up in the same clip.
+ */
+export function getEventRanges(demo: Demo): TickRange[] {
+  const interval = demo.getTickInterval();
+  const before = Math.round(CLIP_PADDING_BEFORE / interval);
+  const after = Math.round(CLIP_PADDING_AFTER / interval);
+  const ranges: TickRange[] = [];
+
+  getSortedEvents(demo).forEach((event) => {
+    const start = Math.max(0, event.tick - before);
+    const end = event.tick + after;
+    const previous = ranges[ranges.length - 1];
+    if (previous !== undefined && start <= previous.end) {
+      previous.end = Math.max(previous.end, end);
+    } else {
+      ranges.push({ start, end });
+    }
+  });
+  return ranges;
+}
+
+// See https://github.com/advancedfx/advancedfx/wiki/Source%3Amirv_cmd
+export function formatEventsAsMirvScript(demo: Demo) {
+  const lines = ["mirv_cmd clear"];
+  getEventRanges(demo).forEach(({ start, end }) => {
+    lines.push(`mirv_cmd addAtTick ${start} "mirv_streams record start"`);
+    lines.push(`mirv_cmd addAtTick ${end} "mirv_streams record end"`);
+  });
+  lines.push("");
+  return lines.join("\n");
+}
+
+export type RecordingTool = "svr" | "lawena";
+
+/**
+ * Formats a VDM file that records a clip of each event range while the
+ * demo plays and skips the parts in between. Source Video Render records
+ * with startmovie/endmovie, Lawena's config provides the
+ * startrecording/stoprecording aliases.
+ */
+export function formatEventsAsRecordingVdm(demo: Demo, tool: RecordingTool) {
+  const name = demo.getShortName();
+  const actions: VdmAction[] = [];
+  let position = 1;
+  getEventRanges(demo).forEach(({ start, end }, index) => {
+    if (start > position) {
+      actions.push({
+        factory: "SkipAhead",
+        name: `Skip to clip ${index + 1}`,
+        starttick: position.toString(),
+        skiptotick: start.toString(),
+      });
+    }
+    actions.push({
+      factory: "PlayCommands",
+      name: `Start clip ${index + 1}`,
+      starttick: start.toString(),
+      commands:
+        tool === "svr"
+          ? `startmovie ${name}_${index + 1}.mp4`
+          : "startrecording",
+    });
+    actions.push({
+      factory: "PlayCommands",
+      name: `End clip ${index + 1}`,
+      starttick: end.toString(),
+      commands: tool === "svr" ? "endmovie" : "stoprecording",
+    });
+    position = end + 1;
+  });
+  if (actions.length !== 0) {
+    actions.push({
+      factory: "PlayCommands",
+      name: "Stop",
+      starttick: position.toString(),
+      commands: "stopdemo",
+    });
+  }
+  const comments =
+    tool === "svr"
+      ? [`Start ${name}.dem through the SVR launcher to record the clips.`]
+      : [
+          `Start TF2 through Lawena and play ${name}.dem to record the clips.`,
+        ];
+  return formatVdm(actions, comments);
+}
+
+function formatEventsDate(date: Date) {
+  const pad = (value: number) => value.toString().padStart(2, "0");
+  return (
+    `${date.getFullYear()}/${pad(date.getMonth() + 1)}/${pad(date.getDate())}` +
+    ` ${pad(date.getHours())}:${pad(date.getMinutes())}`
+  );
+}
+
+/**
+ * Formats the events of all demos like TF2's _events.txt, which is read by
+ * clip recording tools like Ryukbot. Each demo's events start with a ">".
+ */
+export function formatEventsForClipTools(demos: Demo[]) {
+  const lines: string[] = [];
+  demos
+    .filter((demo) => demo.events.length !== 0)
+    .forEach((demo) => {
+      const date = formatEventsDate(new Date(demo.birthtime));
+      const name = demo.getShortName();
+      lines.push(">");
+      getSortedEvents(demo).forEach((event) => {
+        const description =
+          event.name === "Killstreak"
+            ? `Killstreak ${event.value}`
+            : `Bookmark ${event.value}`;
+        lines.push(`[${date}] ${description} ("${name}" at ${event.tick})`);
+      });
+    });
+  lines.push("");
+  return lines.join("\n");
+}
@@ -0,0 +1,129 @@
+import fs from "fs";
+import path from "path";
+import log from "electron-log";
+
+import { Demo } from "./Demos";
+import rconConnection from "./Rcon";
+import { getTf2Dir } from "./Tf2Config";
+import { normalizeFilePath } from "./util";
+
+// Values for spec_mode, see OBS_MODE_* in the Source SDK
+export enum SpectatorMode {
+  FirstPerson = 4,
+  ThirdPerson = 5,
+  FreeCamera = 6,
+}
+
+// Whether a recording was started from DemoMan and hasn't been stopped yet.
+// Recordings started or stopped in-game aren't reflected here.
+let recording = false;
+
+// Quotes can't be escaped in console commands, so they are removed instead
+function quote(arg: string) {
+  return `"${arg.replace(/"/g, "")}"`;
+}
+
+// playdemo expects paths relative to the tf directory
+export function getPlaydemoPath(demo: Demo, tfDir: string) {
+  if (!demo.isTf2Demo()) {
+    throw new Error(
+      `Demo ${demo.getShortName()} was recorded in another game ` +
+        `(${demo.header.gameDir}), TF2 can't play it`
+    );
+  }
+  const relativePath = path.relative(
+    normalizeFilePath(tfDir),
+    normalizeFilePath(demo.filename)
+  );
+  if (relativePath.startsWith("..") || path.isAbsolute(relativePath)) {
+    throw new Error(`Demo ${demo.filename} is outside of the tf directory`);
+  }
+  return relativePath.split(path.sep).join("/");
+}
+
+export async function playDemo(demo: Demo) {
+  const tfDir = getTf2Dir();
+  if (tfDir === undefined) {
+    throw new Error("Could not find the TF2 directory");
+  }
+  log.info(`Playing demo ${demo.filename}`);
+  await rconConnection.execute(`playdemo ${getPlaydemoPath(demo, tfDir)}`);
+}
+
+/**
+ * Jumps the currently playing demo to the given tick.
+ * Requires an established RCON connection to the game.
+ */
+export async function skipToTick(tick: number, pauseAfter: boolean) {
+  log.info(`Skipping demo playback to tick ${tick}`);
+  const cmds = [`demo_gototick ${tick}`];
+  if (pauseAfter) {
+    cmds.push("demo_pause");
+  }
+  await rconConnection.executeAll(cmds);
+}
+
+export async function spectatePlayer(name: string) {
+  log.info(`Spectating player ${name}`);
+  await rconConnection.execute(`spec_player ${quote(name)}`);
+}
+
+export async function setSpectatorMode(mode: SpectatorMode) {
+  await rconConnection.execute(`spec_mode ${mode}`);
+}
+
+export async function setDemoTimescale(timescale: number) {
+  log.info(`Setting demo timescale to ${timescale}`);
+  await rconConnection.execute(`demo_timescale ${timescale}`);
+}
+
+export function isRecording() {
+  return recording;
+}
+
+/**
+ * Starts recording a demo using TF2's demo support,
+ * which names the demo and handles the demo folder itself.
+ */
+export async function startRecording() {
+  log.info("Starting demo recording");
+  await rconConnection.execute("ds_record");
+  recording = true;
+}
+
+export async function stopRecording() {
+  log.info("Stopping demo recording");
+  await rconConnection.execute("ds_stop");
+  recording = false;
+}
+
+/**
+ * Dumps the game console to a file using condump and returns its contents.
+ * condump writes to tf/condumpXXX.txt, using the first unused number.
+ */
+export async function getCondump() {
+  const tfDir = getTf2Dir();
+  if (tfDir === undefined) {
+    throw new Error("Could not find the TF2 directory");
+  }
+  const startTime = Date.now();
+  await rconConnection.execute("condump");
+
+  const dumps = (await fs.promises.readdir(tfDir))
+    .filter((file) => /^condump\d+\.txt$/.test(file))
+    .map((file) => path.join(tfDir, file));
+  const stats = await Promise.all(dumps.map((file) => fs.promises.stat(file)));
+  let newest: string | undefined;
+  let newestTime = startTime - 1000;
+  stats.forEach((stat, index) => {
+    if (stat.mtimeMs >= newestTime) {
+      newest = dumps[index];
+      newestTime = stat.mtimeMs;
+    }
+  });
+  if (newest === undefined) {
+    throw new Error("Could not find the console dump");
+  }
+  log.info(`Reading console dump ${newest}`);
+  return (await fs.promises.readFile(newest)).toString();
+}
@@ -0,0 +1,219 @@
+import React from "react";
+import fs from "fs";
+import { remote } from "electron";
+import log from "electron-log";
+
+import Button from "@material-ui/core/Button";
+import DialogContentText from "@material-ui/core/DialogContentText";
+import Typography from "@material-ui/core/Typography";
+
+import SmallDialog from "./SmallDialog";
+import rconConnection from "./Rcon";
+import launchTf2, { connectToGame } from "./LaunchTf2";
+import {
+  getCondump,
+  isRecording,
+  startRecording,
+  stopRecording,
+} from "./GameCommands";
+import isTf2Running from "./Tf2Process";
+import ServerStatusPanel from "./ServerStatusPanel";
+
+type GameDialogProps = {
+  ref: React.RefObject<GameDialog>;
+};
+
+type GameDialogState = {
+  open: boolean;
+  connected: boolean;
+  recording: boolean;
+  // Whether the game is running, null while checking
+  tf2Running: boolean | null;
+  // Text of the button whose action is running, e.g. "Launching..."
+  busy: string | null;
+  error: string | null;
+};
+
+/**
+ * Shows whether DemoMan is connected to the game over RCON, and lets the
+ * user connect to a running game or launch it with RCON enabled.
+ */
+export default class GameDialog extends React.Component<
+  GameDialogProps,
+  GameDialogState
+> {
+  constructor(props: GameDialogProps) {
+    super(props);
+    this.state = {
+      open: false,
+      connected: rconConnection.connected,
+      recording: isRecording(),
+      tf2Running: null,
+      busy: null,
+      error: null,
+    };
+  }
+
+  componentDidMount() {
+    rconConnection.on("connected", this.connectionChanged);
+    rconConnection.on("disconnected", this.connectionChanged);
+  }
+
+  componentWillUnmount() {
+    rconConnection.off("connected", this.connectionChanged);
+    rconConnection.off("disconnected", this.connectionChanged);
+  }
+
+  connectionChanged = () => {
+    this.setState({
+      connected: rconConnection.connected,
+      recording: isRecording(),
+    });
+  };
+
+  toggleRecording = () => {
+    const { recording } = this.state;
+    const busy = recording ? "Stopping recording..." : "Starting recording...";
+    this.run(busy, async () => {
+      await (recording ? stopRecording() : startRecording());
+      this.setState({ recording: isRecording() });
+    });
+  };
+
+  open = () => {
+    this.setState({ open: true, error: null });
+    this.checkTf2Running();
+  };
+
+  checkTf2Running = async () => {
+    this.setState({ tf2Running: null });
+    const tf2Running = await isTf2Running();
+    this.setState({ tf2Running });
+  };
+
+  close = () => {
+    this.setState({ open: false });
+  };
+
+  // Runs the action, showing its errors in the dialog
+  run = async (busy: string, action: () => Promise<void>) => {
+    this.setState({ busy, error: null });
+    try {
+      await action();
+    } catch (e) {
+      log.warn(`${busy} failed: ${e}`);
+      this.setState({ error: e.message });
+    }
+    this.setState({ busy: null });
+    this.checkTf2Running();
+  };
+
+  saveConsoleLog = () => {
+    const savePath = remote.dialog.showSaveDialogSync({
+      title: "Save console log",
+      defaultPath: "console.txt",
+      filters: [{ name: "Text", extensions: ["txt"] }],
+    });
+    // This happens when the user cancels the dialog
+    if (savePath === undefined) {
+      return;
+    }
+    this.run("Saving console log...", async () => {
+      await fs.promises.writeFile(savePath, await getCondump());
+    });
+  };
+
+  getStatusText() {
+    const { connected, recording, tf2Running, busy } = this.state;
+    if (busy !== null) {
+      return busy;
+    }
+    if (!connected && tf2Running) {
+      return (
+        "TF2 is running, but DemoMan is not connected to it. The game " +
+        "needs to be started with -usercon and an rcon_password."
+      );
+    }
+    if (!connected) {
+      return (
+        "TF2 is not running. Launch it with the options DemoMan needs " +
+        "from here."
+      );
+    }
+    return recording
+      ? "DemoMan is connected to TF2 and recording a demo."
+      : "DemoMan is connected to TF2.";
+  }
+
+  render() {
+    const { open, connected, recording, tf2Running, busy, error } =
+      this.state;
+    return (
+      <SmallDialog
+        title="TF2"
+        open={open}
+        onClose={this.close}
+        maxWidth={connected ? "sm" : "xs"}
+        actions={
+          <>
+            <Button variant="contained" onClick={this.close}>
+              Close
+            </Button>
+            {connected ? (
+              <>
+                <Button
+                  variant="contained"
+                  onClick={() => rconConnection.disconnect()}
+                >
+                  Disconnect
+                </Button>
+                <Button
+                  variant="contained"
+                  disabled={busy !== null}
+                  onClick={this.saveConsoleLog}
+                >
+                  Save console log
+                </Button>
+                <Button
+                  variant="contained"
+                  color={recording ? "secondary" : "primary"}
+                  disabled={busy !== null}
+                  onClick={this.toggleRecording}
+                >
+                  {recording ? "Stop recording" : "Record demo"}
+                </Button>
+              </>
+            ) : (
+              <>
+                <Button
+                  variant="contained"
+                  color={tf2Running ? "primary" : "default"}
+                  disabled={busy !== null || tf2Running === false}
+                  onClick={() => {
+                    this.run("Connecting...", connectToGame);
+                  }}
+                >
+                  Connect
+                </Button>
+                <Button
+                  variant="contained"
+                  color={tf2Running ? "default" : "primary"}
+                  disabled={busy !== null || tf2Running === true}
+                  onClick={() => {
+                    this.run("Launching TF2...", () => launchTf2());
+                  }}
+                >
+                  Launch TF2
+                </Button>
+              </>
+            )}
+          </>
+        }
+      >
+        <DialogContentText>{this.getStatusText()}</DialogContentText>
+        {error !== null && <Typography color="error">{error}</Typography>}
+        {connected && <ServerStatusPanel />}
+      </SmallDialog>
+    );
+  }
+}
@@ -0,0 +1,81 @@
+import crypto from "crypto";
+import fs from "fs";
+import path from "path";
+import { remote } from "electron";
+import log from "electron-log";
+
+import { DemoHeader } from "./DemoHeader";
+import { normalizeDirPath } from "./util";
+
+interface HeaderCacheEntry {
+  size: number;
+  mtimeMs: number;
+  header: DemoHeader;
+}
+
+/**
+ * Stores the headers of the demos in a directory, so scanning it again
+ * doesn't have to open every demo file. Entries are invalidated when the
+ * file's size or modification time changes.
+ */
+export default class HeaderCache {
+  cachePath: string;
+
+  entries: Record<string, HeaderCacheEntry>;
+
+  // Entries used since loading, all others are dropped when saving
+  usedEntries: Record<string, HeaderCacheEntry> = {};
+
+  constructor(cachePath: string, entries: Record<string, HeaderCacheEntry>) {
+    this.cachePath = cachePath;
+    this.entries = entries;
+  }
+
+  static async load(dirPath: string) {
+    const hash = crypto
+      .createHash("sha1")
+      .update(normalizeDirPath(dirPath))
+      .digest("hex");
+    const cachePath = path.join(
+      remote.app.getPath("userData"),
+      "header_cache",
+      `${hash}.json`
+    );
+    let entries = {};
+    try {
+      entries = JSON.parse((await fs.promises.readFile(cachePath)).toString());
+    } catch (e) {
+      log.debug(`No header cache for ${dirPath}`);
+    }
+    return new HeaderCache(cachePath, entries);
+  }
+
+  get(filename: string, stats: fs.Stats): DemoHeader | undefined {
+    const entry = this.entries[path.basename(filename)];
+    if (
+      entry === undefined ||
+      entry.size !== stats.size ||
+      entry.mtimeMs !== stats.mtimeMs
+    ) {
+      return undefined;
+    }
+    this.usedEntries[path.basename(filename)] = entry;
+    return entry.header;
+  }
+
+  set(filename: string, stats: fs.Stats, header: DemoHeader) {
+    this.usedEntries[path.basename(filename)] = {
+      size: stats.size,
+      mtimeMs: stats.mtimeMs,
+      header,
+    };
+  }
+
+  async save() {
+    await fs.promises.mkdir(path.dirname(this.cachePath), { recursive: true });
+    await fs.promises.writeFile(
+      this.cachePath,
+      JSON.stringify(this.usedEntries)
+    );
+  }
+}
@@ -0,0 +1,88 @@
+import fs from "fs";
+import https from "https";
+
+export function request(
+  url: string,
+  options: https.RequestOptions = {},
+  body?: string
+) {
+  return new Promise<string>((resolve, reject) => {
+    const req = https.request(url, options, (response) => {
+      let responseBody = "";
+      response.on("data", (chunk) => {
+        responseBody += chunk;
+      });
+      response.on("end", () => {
+        const status = response.statusCode ?? 0;
+        if (status < 200 || status >= 300) {
+          reject(new Error(`Request failed with ${status}: ${responseBody}`));
+          return;
+        }
+        resolve(responseBody);
+      });
+    });
+    req.on("error", reject);
+    if (body !== undefined) {
+      req.write(body);
+    }
+    req.end();
+  });
+}
+
+export async function getJson<T>(url: string): Promise<T> {
+  return JSON.parse(await request(url));
+}
+
+/**
+ * Downloads the URL to the file. If contentType is given, responses of
+ * other types are rejected, e.g. "image/" for any image. The file is only
+ * created once the download is complete.
+ */
+export function download(url: string, filePath: string, contentType = "") {
+  const partPath = `${filePath}.part`;
+  return new Promise<void>((resolve, reject) => {
+    const fail = (e: Error) => {
+      fs.rmSync(partPath, { force: true });
+      reject(e);
+    };
+    https
+      .get(url, (response) => {
+        if (response.statusCode !== 200) {
+          response.resume();
+          reject(new Error(`Request failed with ${response.statusCode}`));
+          return;
+        }
+        const type = response.headers["content-type"] ?? "";
+        if (!type.startsWith(contentType)) {
+          response.resume();
+          reject(new Error(`Unexpected content type ${type}`));
+          return;
+        }
+        const file = fs.createWriteStream(partPath);
+        response.pipe(file);
+        response.on("aborted", () => {
+          file.destroy();
+          fail(new Error("Download was aborted"));
+        });
+        response.on("error", (e) => {
+          file.destroy();
+          fail(e);
+        });
+        file.on("finish", () => {
+          if (!response.complete) {
+            fail(new Error("Download was cut off"));
+            return;
+          }
+          try {
+            fs.renameSync(partPath, filePath);
+          } catch (e) {
+            fail(e);
+            return;
+          }
+          resolve();
+        });
+        file.on("error", fail);
+      })
+      .on("error", reject);
+  });
+}
@@ -0,0 +1,148 @@
+import crypto from "crypto";
+import { EventEmitter } from "events";
+import log from "electron-log";
+
+// Finished jobs are kept so failures can still be looked at
+const MAX_FINISHED_JOBS = 20;
+
+export type JobType = "scan" | "upload" | "export" | "repair";
+
+export type JobStatus = "running" | "done" | "failed" | "cancelled";
+
+export interface Job {
+  id: string;
+  type: JobType;
+  description: string;
+  status: JobStatus;
+  // Between 0 and 1, null while the job can't tell
+  progress: number | null;
+  cancellable: boolean;
+  startTime: number;
+  error?: string;
+}
+
+// Thrown by jobs that stopped because they were cancelled
+export class JobCancelledError extends Error {
+  constructor() {
+    super("Cancelled");
+    this.name = "JobCancelledError";
+  }
+}
+
+export interface JobContext {
+  setProgress: (progress: number) => void;
+  // Makes the job cancellable, the function has to make the task
+  // reject with a JobCancelledError
+  onCancel: (cancel: () => void) => void;
+}
+
+/**
+ * Keeps track of long-running tasks like scans, uploads and exports so
+ * they can be listed and cancelled in one place. Emits a "changed" event
+ * with the jobs whenever a job starts, progresses or finishes.
+ */
+class JobManager extends EventEmitter {
+  private jobs: Job[] = [];
+
+  private cancelHandlers = new Map<string, () => void>();
+
+  getJobs() {
+    return this.jobs;
+  }
+
+  /**
+   * Runs the task as a job and returns its result. Errors are passed on
+   * after they're recorded in the job.
+   */
+  async run<T>(
+    type: JobType,
+    description: string,
+    task: (context: JobContext) => Promise<T>
+  ) {
+    const id = crypto.randomBytes(8).toString("hex");
+    this.jobs = [
+      ...this.jobs,
+      {
+        id,
+        type,
+        description,
+        status: "running",
+        progress: null,
+        cancellable: false,
+        startTime: Date.now(),
+      },
+    ];
+    this.emit("changed", this.jobs);
+
+    let lastProgress: number | null = null;
+    const context: JobContext = {
+      setProgress: (progress) => {
+        // Uploads report every chunk, whole percents are enough
+        const rounded = Math.floor(progress * 100) / 100;
+        if (rounded !== lastProgress) {
+          lastProgress = rounded;
+          this.updateJob(id, { progress: rounded });
+        }
+      },
+      onCancel: (cancel) => {
+        this.cancelHandlers.set(id, cancel);
+        this.updateJob(id, { cancellable: true });
+      },
+    };
+    try {
+      const result = await task(context);
+      this.finishJob(id, { status: "done", progress: 1 });
+      return result;
+    } catch (e) {
+      if (e instanceof JobCancelledError) {
+        this.finishJob(id, { status: "cancelled" });
+      } else {
+        this.finishJob(id, { status: "failed", error: e.message });
+      }
+      throw e;
+    }
+  }
+
+  // Returns false if the job isn't running or can't be cancelled
+  cancel(id: string) {
+    const cancel = this.cancelHandlers.get(id);
+    if (cancel === undefined) {
+      return false;
+    }
+    log.info(`Cancelling job ${id}`);
+    this.cancelHandlers.delete(id);
+    this.updateJob(id, { cancellable: false });
+    cancel();
+    return true;
+  }
+
+  clearFinished() {
+    this.jobs = this.jobs.filter((job) => job.status === "running");
+    this.emit("changed", this.jobs);
+  }
+
+  private updateJob(id: string, update: Partial<Job>) {
+    this.jobs = this.jobs.map((job) =>
+      job.id === id ? { ...job, ...update } : job
+    );
+    this.emit("changed", this.jobs);
+  }
+
+  private finishJob(id: string, update: Partial<Job>) {
+    this.cancelHandlers.delete(id);
+    const finished = this.jobs.filter(
+      (job) => job.status !== "running" && job.id !== id
+    );
+    // Drops the oldest finished jobs
+    const dropped = new Set(
+      finished
+        .slice(0, Math.max(0, finished.length + 1 - MAX_FINISHED_JOBS))
+        .map((job) => job.id)
+    );
+    this.jobs = this.jobs.filter((job) => !dropped.has(job.id));
+    this.updateJob(id, { ...update, cancellable: false });
+  }
+}
+
+const jobManager = new JobManager();
+export default jobManager;
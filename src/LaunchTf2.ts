@@ -0,0 +1,108 @@
+import crypto from "crypto";
+import { shell } from "electron";
+import log from "electron-log";
+
+import rconConnection, { createRconError } from "./Rcon";
+import { discoverRconSettings } from "./Tf2Config";
+import isTf2Running from "./Tf2Process";
+
+const CONNECT_INTERVAL = 2000;
+
+// Password set when DemoMan launched the game, used for reconnecting
+let launchPassword: string | undefined;
+
+function tryConnect(port: number, password: string) {
+  return new Promise<void>((resolve, reject) => {
+    rconConnection.connect(
+      port,
+      password,
+      () => resolve(),
+      (reason) => reject(createRconError(reason))
+    );
+  });
+}
+
+function sleep(ms: number) {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+/**
+ * Connects to a running game, using the RCON settings found in the
+ * user's configs or the password set when DemoMan launched the game.
+ * Does nothing if already connected.
+ */
+export async function connectToGame() {
+  if (rconConnection.connected) {
+    return;
+  }
+  const settings = discoverRconSettings();
+  const password = launchPassword ?? settings.password;
+  if (password === undefined) {
+    throw new Error(
+      "No RCON password was found in your TF2 configs. Launch TF2 from " +
+        "DemoMan or set rcon_password in your autoexec.cfg."
+    );
+  }
+  log.info("Connecting to TF2");
+  try {
+    await tryConnect(settings.port, password);
+  } catch (e) {
+    if (e.code === "ECONNREFUSED" && !(await isTf2Running())) {
+      throw new Error("TF2 isn't running.");
+    }
+    throw e;
+  }
+}
+
+/**
+ * Starts TF2 through Steam with the options needed for RCON
+ * and resolves once the game accepts RCON connections.
+ */
+export default async function launchTf2(timeout = 120000) {
+  // Steam ignores the launch options if the game is already running
+  if (await isTf2Running()) {
+    try {
+      await connectToGame();
+      return;
+    } catch (e) {
+      log.warn(`Could not connect to the running game: ${e.code}`);
+      throw new Error(
+        "TF2 is already running without the options DemoMan needs. " +
+          "Close it and launch it again from DemoMan."
+      );
+    }
+  }
+  const settings = discoverRconSettings();
+  // If the user hasn't set a password, any password will do,
+  // since we are the ones setting it on launch.
+  const password = settings.password ?? crypto.randomBytes(16).toString("hex");
+  const args = [
+    "-usercon",
+    "+ip 127.0.0.1",
+    `+hostport ${settings.port}`,
+    `+rcon_password ${password}`,
+    "+net_start",
+  ].join(" ");
+
+  log.info("Launching TF2");
+  await shell.openExternal(`steam://run/440//${encodeURIComponent(args)}/`);
+  launchPassword = password;
+
+  const deadline = Date.now() + timeout;
+  for (;;) {
+    try {
+      // eslint-disable-next-line no-await-in-loop
+      await tryConnect(settings.port, password);
+      log.info("TF2 is ready for RCON connections");
+      return;
+    } catch (e) {
+      // A wrong password won't fix itself by waiting
+      if (e.code === "EPASSWD" || Date.now() > deadline) {
+        log.warn(`Could not connect to TF2: ${e.code}`);
+        throw e;
+      }
+    }
+    // eslint-disable-next-line no-await-in-loop
+    await sleep(CONNECT_INTERVAL);
+  }
+}
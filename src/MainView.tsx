@@ -1,4 +1,5 @@
 import React from "react";
+import { ipcRenderer } from "electron";
 import cfg from "electron-cfg";
 import log from "electron-log";
 
@@ -9,6 +10,11 @@ import DemoDetails from "./DemoDetailsView";
 import SettingsDialog from "./SettingsDialog";
 import { InfoDialog, DemoListInfo } from "./InfoDialog";
 import AutoDeleteDialog from "./AutoDeleteDialog";
+import GameDialog from "./GameDialog";
+import setPresence from "./DiscordRpc";
+import uploadQueue from "./UploadQueue";
+import { clearPlaylist } from "./Playlist";
+import { migrateCredentials } from "./Credentials";
 
 type MainViewState = {
   selectDemoPathDialogOpen: boolean;
@@ -17,6 +23,7 @@ type MainViewState = {
   settings: React.RefObject<SettingsDialog>;
   info: React.RefObject<InfoDialog>;
   autoDeleteDialog: React.RefObject<AutoDeleteDialog>;
+  gameDialog: React.RefObject<GameDialog>;
 };
 
 export default class MainView extends React.Component<
@@ -31,10 +38,59 @@ export default class MainView extends React.Component<
       settings: React.createRef(),
       info: React.createRef(),
       autoDeleteDialog: React.createRef(),
+      gameDialog: React.createRef(),
       selectDemoPathDialogOpen: !cfg.has("demo_path"),
     };
   }
 
+  componentDidMount() {
+    setPresence("Browsing demos");
+    // Removes VDM files left over from a playlist played before a restart
+    clearPlaylist();
+    // Uploads need the demos.tf API key, which may have to be moved first
+    migrateCredentials()
+      .catch((e) => log.error(`Error migrating credentials: ${e}`))
+      .finally(() => uploadQueue.start());
+    ipcRenderer.on("open-demo", (_event, demoPath: string) => {
+      this.openDemoFile(demoPath);
+    });
+    document.addEventListener("dragover", this.handleDragOver);
+    document.addEventListener("drop", this.handleDrop);
+  }
+
+  componentWillUnmount() {
+    ipcRenderer.removeAllListeners("open-demo");
+    document.removeEventListener("dragover", this.handleDragOver);
+    document.removeEventListener("drop", this.handleDrop);
+  }
+
+  // Opens a demo file that isn't necessarily in the demo folder,
+  // e.g. one opened through the OS or dropped onto the window.
+  openDemoFile = async (demoPath: string) => {
+    let demo;
+    try {
+      demo = await Demo.create(demoPath);
+    } catch (e) {
+      log.error(`Error opening demo ${demoPath}: ${e}`);
+      return;
+    }
+    this.viewDemo(demo);
+  };
+
+  handleDragOver = (event: DragEvent) => {
+    event.preventDefault();
+  };
+
+  handleDrop = (event: DragEvent) => {
+    event.preventDefault();
+    const files = Array.from(event.dataTransfer?.files ?? []);
+    // Electron adds the path of dropped files to the File object
+    const demoFile = files.find((file) => /\.dem$/i.test(file.name));
+    if (demoFile !== undefined) {
+      this.openDemoFile(demoFile.path);
+    }
+  };
+
   viewDemo = (demo: Demo) => {
     const { demoDetails } = this.state;
     log.debug(`Viewing demo ${demo.filename}`);
@@ -63,6 +119,11 @@ export default class MainView extends React.Component<
     autoDeleteDialog.current?.open();
   };
 
+  viewGameDialog = () => {
+    const { gameDialog } = this.state;
+    gameDialog.current?.open();
+  };
+
   render() {
     const {
       table,
@@ -70,6 +131,7 @@ export default class MainView extends React.Component<
       demoDetails,
       settings,
       autoDeleteDialog,
+      gameDialog,
       info,
     } = this.state;
     return (
@@ -80,6 +142,7 @@ export default class MainView extends React.Component<
           viewSettings={this.viewSettings}
           viewInfoDialog={this.viewInfoDialog}
           viewAutoDeleteDialog={this.viewAutoDeleteDialog}
+          viewGameDialog={this.viewGameDialog}
         />
         <SelectDemoPathDialog
           open={selectDemoPathDialogOpen}
@@ -94,11 +157,13 @@ export default class MainView extends React.Component<
           ref={demoDetails}
           demo={null}
           onClose={() => {
+            setPresence("Browsing demos");
             table.current?.RefreshDemoList();
           }}
         />
         <SettingsDialog ref={settings} />
         <InfoDialog ref={info} />
+        <GameDialog ref={gameDialog} />
         <AutoDeleteDialog
           ref={autoDeleteDialog}
           onClose={() => {
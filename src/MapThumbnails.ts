@@ -0,0 +1,62 @@
+import fs from "fs";
+import path from "path";
+import { pathToFileURL } from "url";
+import { remote } from "electron";
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import { download } from "./Http";
+
+// Matches version suffixes like _rc2, _b4a, _f12 or _final1
+const VERSION_SUFFIX = /_(?:(?:rc|b|a|f|v)\d+[a-z]?|final\d*)$/i;
+
+// Maps for which no thumbnail could be downloaded during this session
+const unavailableMaps = new Set<string>();
+
+function getThumbnailDir() {
+  return path.join(remote.app.getPath("userData"), "map_thumbnails");
+}
+
+// Returns the map name followed by its name without a version suffix,
+// e.g. cp_process_f12 -> cp_process
+function getMapNameCandidates(mapName: string) {
+  const baseName = mapName.replace(VERSION_SUFFIX, "");
+  return baseName !== mapName ? [mapName, baseName] : [mapName];
+}
+
+/**
+ * Returns a file URL to a thumbnail image of the map, or undefined if there
+ * is none. Thumbnails are downloaded from the URL template set as
+ * map_thumbnail_url (e.g. "https://example.com/{map}.jpg") and cached.
+ */
+export default async function getMapThumbnail(mapName: string) {
+  const urlTemplate: string = cfg.get("map_thumbnail_url", "");
+  if (urlTemplate === "" || unavailableMaps.has(mapName)) {
+    return undefined;
+  }
+  const thumbnailDir = getThumbnailDir();
+  await fs.promises.mkdir(thumbnailDir, { recursive: true });
+
+  const candidates = getMapNameCandidates(mapName);
+  for (let i = 0; i < candidates.length; i += 1) {
+    const candidate = candidates[i];
+    const thumbnailPath = path.join(thumbnailDir, `${candidate}.img`);
+    if (fs.existsSync(thumbnailPath)) {
+      return pathToFileURL(thumbnailPath).href;
+    }
+    try {
+      log.debug(`Downloading map thumbnail for ${candidate}`);
+      // eslint-disable-next-line no-await-in-loop
+      await download(
+        urlTemplate.replace("{map}", encodeURIComponent(candidate)),
+        thumbnailPath,
+        "image/"
+      );
+      return pathToFileURL(thumbnailPath).href;
+    } catch (e) {
+      log.debug(`No map thumbnail found for ${candidate}: ${e}`);
+    }
+  }
+  unavailableMaps.add(mapName);
+  return undefined;
+}
@@ -0,0 +1,52 @@
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import { request } from "./Http";
+import { getCredential } from "./Credentials";
+
+// Accepts the text as the request body and responds with the paste's URL
+export const DEFAULT_PASTE_URL = "https://paste.rs/";
+
+async function createGist(title: string, content: string, token: string) {
+  const response = await request(
+    "https://api.github.com/gists",
+    {
+      method: "POST",
+      headers: {
+        Authorization: `token ${token}`,
+        Accept: "application/vnd.github.v3+json",
+        "Content-Type": "application/json",
+        "User-Agent": "DemoMan",
+      },
+    },
+    JSON.stringify({
+      description: title,
+      public: false,
+      files: { [`${title}.md`]: { content } },
+    })
+  );
+  return JSON.parse(response).html_url as string;
+}
+
+/**
+ * Uploads the text to a GitHub gist if a token is set,
+ * or the configured paste service otherwise. Returns the URL.
+ */
+export default async function sharePaste(title: string, content: string) {
+  const gistToken = await getCredential("gist_token");
+  if (gistToken !== "") {
+    log.info(`Sharing ${title} as a gist`);
+    return createGist(title, content, gistToken);
+  }
+  const pasteUrl: string = cfg.get("paste_url", "") || DEFAULT_PASTE_URL;
+  log.info(`Sharing ${title} on ${pasteUrl}`);
+  const response = await request(
+    pasteUrl,
+    {
+      method: "POST",
+      headers: { "Content-Type": "text/plain; charset=utf-8" },
+    },
+    content
+  );
+  return response.trim();
+}
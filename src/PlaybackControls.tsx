@@ -0,0 +1,142 @@
+import React from "react";
+import { remote } from "electron";
+import log from "electron-log";
+
+import Button from "@material-ui/core/Button";
+import ButtonGroup from "@material-ui/core/ButtonGroup";
+import Grid from "@material-ui/core/Grid";
+import TextField from "@material-ui/core/TextField";
+import Typography from "@material-ui/core/Typography";
+import PlayArrowIcon from "@material-ui/icons/PlayArrow";
+
+import { Demo } from "./Demos";
+import {
+  playDemo,
+  setDemoTimescale,
+  setSpectatorMode,
+  spectatePlayer,
+  SpectatorMode,
+} from "./GameCommands";
+
+const TIMESCALES = [0.25, 0.5, 1, 2];
+
+const SPECTATOR_MODES: [SpectatorMode, string][] = [
+  [SpectatorMode.FirstPerson, "First person"],
+  [SpectatorMode.ThirdPerson, "Third person"],
+  [SpectatorMode.FreeCamera, "Free camera"],
+];
+
+type PlaybackControlsProps = {
+  demo: Demo;
+  // Tick the game is at while it plays this demo
+  playbackTick: number | null;
+};
+
+type PlaybackControlsState = {
+  playerName: string;
+};
+
+/**
+ * Controls for playing the demo in the connected game.
+ * Only shown while DemoMan is connected to TF2.
+ */
+export default class PlaybackControls extends React.Component<
+  PlaybackControlsProps,
+  PlaybackControlsState
+> {
+  constructor(props: PlaybackControlsProps) {
+    super(props);
+    this.state = { playerName: props.demo.header.clientName };
+  }
+
+  // Runs the game command, showing an error box if it fails
+  run = async (description: string, command: () => Promise<void>) => {
+    try {
+      await command();
+    } catch (e) {
+      log.error(`Error running game command: ${e}`);
+      remote.dialog.showErrorBox(`Could not ${description}`, e.message);
+    }
+  };
+
+  render() {
+    const { demo, playbackTick } = this.props;
+    const { playerName } = this.state;
+    return (
+      <Grid container direction="column" alignItems="center" spacing={1}>
+        <Grid item>
+          <Typography variant="subtitle2">
+            {playbackTick === null
+              ? "Playback in TF2"
+              : `Playing in TF2 at tick ${playbackTick}`}
+          </Typography>
+        </Grid>
+        <Grid item>
+          <Button
+            variant="outlined"
+            startIcon={<PlayArrowIcon />}
+            onClick={() => this.run("play the demo", () => playDemo(demo))}
+          >
+            Play demo
+          </Button>
+        </Grid>
+        <Grid item>
+          <ButtonGroup variant="outlined" size="small">
+            {TIMESCALES.map((timescale) => (
+              <Button
+                key={timescale}
+                onClick={() => {
+                  this.run("change the playback speed", () =>
+                    setDemoTimescale(timescale)
+                  );
+                }}
+              >
+                {timescale}x
+              </Button>
+            ))}
+          </ButtonGroup>
+        </Grid>
+        <Grid item>
+          <ButtonGroup variant="outlined" size="small">
+            {SPECTATOR_MODES.map(([mode, name]) => (
+              <Button
+                key={mode}
+                onClick={() => {
+                  this.run("change the camera", () => setSpectatorMode(mode));
+                }}
+              >
+                {name}
+              </Button>
+            ))}
+          </ButtonGroup>
+        </Grid>
+        <Grid item container justify="center" alignItems="center" spacing={1}>
+          <Grid item>
+            <TextField
+              label="Player"
+              size="small"
+              value={playerName}
+              onChange={(event) => {
+                this.setState({ playerName: event.target.value });
+              }}
+              spellCheck={false}
+            />
+          </Grid>
+          <Grid item>
+            <Button
+              variant="outlined"
+              disabled={playerName.trim() === ""}
+              onClick={() => {
+                this.run("spectate the player", () =>
+                  spectatePlayer(playerName.trim())
+                );
+              }}
+            >
+              Spectate
+            </Button>
+          </Grid>
+        </Grid>
+      </Grid>
+    );
+  }
+}
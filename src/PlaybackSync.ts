@@ -0,0 +1,73 @@
+import { EventEmitter } from "events";
+import log from "electron-log";
+
+import rconConnection from "./Rcon";
+
+// How often the playback position is requested from the game, in ms
+const POLL_INTERVAL = 1000;
+
+export interface PlaybackPosition {
+  // Path of the demo as given to playdemo, relative to the tf directory
+  filename: string;
+  tick: number;
+}
+
+/**
+ * Reads the playing demo and the current tick from the output of
+ * demo_info. Returns null if no demo is playing.
+ */
+export function parseDemoInfo(output: string): PlaybackPosition | null {
+  const file = /^Demo contents for (.+):\s*$/m.exec(output);
+  const tick = /^Current tick:?\s*(\d+)/im.exec(output);
+  if (file === null || tick === null) {
+    return null;
+  }
+  return { filename: file[1], tick: parseInt(tick[1], 10) };
+}
+
+/**
+ * Polls the playback position of the connected game while anything is
+ * listening, and emits "position" with a PlaybackPosition, or null while
+ * no demo is playing.
+ */
+class PlaybackSync extends EventEmitter {
+  private timer?: ReturnType<typeof setInterval>;
+
+  private subscribers = 0;
+
+  // Set while a demo_info command is running, so requests don't pile up
+  // while the game is busy, e.g. skipping ahead
+  private polling = false;
+
+  start() {
+    this.subscribers += 1;
+    if (this.timer === undefined) {
+      this.timer = setInterval(this.poll, POLL_INTERVAL);
+    }
+  }
+
+  stop() {
+    this.subscribers -= 1;
+    if (this.subscribers === 0 && this.timer !== undefined) {
+      clearInterval(this.timer);
+      this.timer = undefined;
+    }
+  }
+
+  private poll = async () => {
+    if (!rconConnection.connected || this.polling) {
+      return;
+    }
+    this.polling = true;
+    try {
+      const output = (await rconConnection.execute("demo_info")) ?? "";
+      this.emit("position", parseDemoInfo(output));
+    } catch (e) {
+      log.debug(`Error polling the playback position: ${e}`);
+    }
+    this.polling = false;
+  };
+}
+
+const playbackSync = new PlaybackSync();
+export default playbackSync;
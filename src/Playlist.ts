@@ -0,0 +1,151 @@
+import fs from "fs";
+import path from "path";
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import { Demo } from "./Demos";
+import { getPlaydemoPath } from "./GameCommands";
+import rconConnection from "./Rcon";
+import { getTf2Dir } from "./Tf2Config";
+import { formatVdm, getVdmPath, VdmAction } from "./Vdm";
+
+// Marks VDM files written by the playlist, so user-made ones aren't touched
+const VDM_COMMENT = "Generated by DemoMan playlist";
+const VDM_MARKER = `// ${VDM_COMMENT}`;
+
+export interface PlaylistEntry {
+  demo: Demo;
+  startTick?: number;
+  endTick?: number;
+}
+
+// VDM files written for the current playlist are stored in the settings,
+// so they can still be removed after a restart.
+const CFG_KEY = "playlist_vdms";
+
+function getGeneratedVdms(): string[] {
+  return cfg.get(CFG_KEY, []);
+}
+
+function isGeneratedVdm(vdmPath: string) {
+  try {
+    return fs.readFileSync(vdmPath).toString().startsWith(VDM_MARKER);
+  } catch (e) {
+    return false;
+  }
+}
+
+function buildVdm(entry: PlaylistEntry, nextCommand: string) {
+  const actions: VdmAction[] = [];
+  if (entry.startTick !== undefined) {
+    actions.push({
+      factory: "SkipAhead",
+      name: "Skip to start",
+      starttick: "1",
+      skiptotick: entry.startTick.toString(),
+    });
+  }
+  const endTick = entry.endTick ?? entry.demo.header.numTicks - 1;
+  // Unfinished demos have no tick count, playback simply stops at their end
+  if (endTick > 0) {
+    actions.push({
+      factory: "PlayCommands",
+      name: "Play next",
+      starttick: endTick.toString(),
+      commands: nextCommand,
+    });
+  }
+  return formatVdm(actions, [VDM_COMMENT]);
+}
+
+function writeVdm(vdmPath: string, content: string) {
+  let existing;
+  try {
+    existing = fs.readFileSync(vdmPath).toString();
+  } catch (e) {
+    if (e.code !== "ENOENT") {
+      throw e;
+    }
+  }
+  if (existing !== undefined && !existing.startsWith(VDM_MARKER)) {
+    throw new Error(`Refusing to overwrite existing VDM file ${vdmPath}`);
+  }
+  log.debug(`Writing playlist VDM file ${vdmPath}`);
+  // Stored first, so the file is removed even if DemoMan closes right away
+  cfg.set(CFG_KEY, [...getGeneratedVdms(), vdmPath]);
+  fs.writeFileSync(vdmPath, content);
+}
+
+/**
+ * Removes the VDM files written for the last playlist, as well as any
+ * left in the demo folder, so playing those demos later doesn't continue
+ * with the next demo of the playlist. Called on startup and before a new
+ * playlist starts.
+ */
+export function clearPlaylist() {
+  const vdmPaths = new Set(getGeneratedVdms());
+  const demoDir: string | undefined = cfg.get("demo_path");
+  if (demoDir !== undefined) {
+    try {
+      fs.readdirSync(demoDir)
+        .filter((file) => file.toLowerCase().endsWith(".vdm"))
+        .forEach((file) => vdmPaths.add(path.join(demoDir, file)));
+    } catch (e) {
+      log.warn(`Error reading ${demoDir}: ${e}`);
+    }
+  }
+  vdmPaths.forEach((vdmPath) => {
+    if (isGeneratedVdm(vdmPath)) {
+      log.debug(`Removing playlist VDM file ${vdmPath}`);
+      fs.rmSync(vdmPath, { force: true });
+    }
+  });
+  cfg.set(CFG_KEY, []);
+}
+
+/**
+ * Plays the given demos one after another. Each demo gets a VDM file that
+ * starts the next demo once the end of the demo (or range) is reached,
+ * so playback continues without DemoMan having to watch the game.
+ */
+export async function playPlaylist(entries: PlaylistEntry[]) {
+  const tfDir = getTf2Dir();
+  if (tfDir === undefined) {
+    throw new Error("Could not find the TF2 directory");
+  }
+  const filenames = entries.map((entry) => entry.demo.filename);
+  if (new Set(filenames).size !== filenames.length) {
+    // A demo can only have one VDM file
+    throw new Error("A demo can only be added to a playlist once");
+  }
+
+  // Without a tick count, the end of a demo can't trigger the next one
+  const unfinished = entries
+    .slice(0, -1)
+    .find(
+      (entry) =>
+        entry.endTick === undefined && entry.demo.header.numTicks <= 0
+    );
+  if (unfinished !== undefined) {
+    throw new Error(
+      `Demo ${unfinished.demo.getShortName()} is unfinished, repair it first`
+    );
+  }
+
+  clearPlaylist();
+  const playdemoPaths = entries.map((entry) =>
+    getPlaydemoPath(entry.demo, tfDir)
+  );
+  entries.forEach((entry, index) => {
+    const nextCommand =
+      index + 1 < entries.length
+        ? `playdemo ${playdemoPaths[index + 1]}`
+        : "stopdemo";
+    writeVdm(getVdmPath(entry.demo), buildVdm(entry, nextCommand));
+  });
+
+  if (entries.length !== 0) {
+    log.info(`Starting playlist with ${entries.length} demos`);
+    await rconConnection.execute(`playdemo ${playdemoPaths[0]}`);
+  }
+}
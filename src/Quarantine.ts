@@ -0,0 +1,123 @@
+import fs from "fs";
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import { InvalidDemoFileError } from "./DemoHeader";
+
+// Number of failed scans after which a demo is skipped
+const MAX_FAILURES = 3;
+
+const CFG_KEY = "quarantined_demos";
+
+export interface QuarantineEntry {
+  size: number;
+  mtimeMs: number;
+  error: string;
+  failures: number;
+}
+
+function getEntries(): Record<string, QuarantineEntry> {
+  return cfg.get(CFG_KEY, {});
+}
+
+/**
+ * Returns the demos that are skipped while scanning, because reading them
+ * failed repeatedly. Demos are only skipped as long as they don't change.
+ */
+export function getQuarantinedDemos() {
+  return Object.entries(getEntries()).filter(
+    ([, entry]) => entry.failures >= MAX_FAILURES
+  );
+}
+
+// Removes the demo from the quarantine, or all demos if none is given
+export function clearQuarantine(filename?: string) {
+  if (filename === undefined) {
+    cfg.set(CFG_KEY, {});
+    return;
+  }
+  const entries = getEntries();
+  delete entries[filename];
+  cfg.set(CFG_KEY, entries);
+}
+
+async function getStats(filename: string) {
+  try {
+    return await fs.promises.stat(filename);
+  } catch (e) {
+    return undefined;
+  }
+}
+
+function isUnchanged(entry: QuarantineEntry, stats: fs.Stats) {
+  return entry.size === stats.size && entry.mtimeMs === stats.mtimeMs;
+}
+
+// Returns the files that aren't quarantined
+export async function filterQuarantined(filenames: string[]) {
+  const entries = getEntries();
+  const quarantined = await Promise.all(
+    filenames.map(async (filename) => {
+      const entry = entries[filename];
+      if (entry === undefined || entry.failures < MAX_FAILURES) {
+        return false;
+      }
+      const stats = await getStats(filename);
+      return stats !== undefined && isUnchanged(entry, stats);
+    })
+  );
+  return filenames.filter((filename, i) => {
+    if (quarantined[i]) {
+      log.debug(`Skipping quarantined demo ${filename}`);
+    }
+    return !quarantined[i];
+  });
+}
+
+/**
+ * Counts a failed read for each file that isn't a valid demo and removes
+ * files that were read successfully from the quarantine. Other errors,
+ * e.g. a file being locked, don't count.
+ */
+export async function recordScanResults(
+  results: [filename: string, error: Error | undefined][]
+) {
+  const entries = getEntries();
+  let changed = false;
+  await Promise.all(
+    results.map(async ([filename, error]) => {
+      if (error === undefined) {
+        if (filename in entries) {
+          delete entries[filename];
+          changed = true;
+        }
+        return;
+      }
+      if (!(error instanceof InvalidDemoFileError)) {
+        return;
+      }
+      const stats = await getStats(filename);
+      if (stats === undefined) {
+        return;
+      }
+      const entry = entries[filename];
+      const failures =
+        entry !== undefined && isUnchanged(entry, stats)
+          ? entry.failures + 1
+          : 1;
+      if (failures === MAX_FAILURES) {
+        log.warn(`Quarantining demo ${filename}: ${error.message}`);
+      }
+      entries[filename] = {
+        size: stats.size,
+        mtimeMs: stats.mtimeMs,
+        error: error.message,
+        failures,
+      };
+      changed = true;
+    })
+  );
+  if (changed) {
+    cfg.set(CFG_KEY, entries);
+  }
+}
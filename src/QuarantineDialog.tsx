@@ -0,0 +1,110 @@
+import React from "react";
+import path from "path";
+
+import Button from "@material-ui/core/Button";
+import List from "@material-ui/core/List";
+import ListItem from "@material-ui/core/ListItem";
+import ListItemText from "@material-ui/core/ListItemText";
+import ListItemSecondaryAction from "@material-ui/core/ListItemSecondaryAction";
+import IconButton from "@material-ui/core/IconButton";
+import Tooltip from "@material-ui/core/Tooltip";
+import DialogContentText from "@material-ui/core/DialogContentText";
+import RefreshIcon from "@material-ui/icons/Refresh";
+
+import SmallDialog from "./SmallDialog";
+import {
+  clearQuarantine,
+  getQuarantinedDemos,
+  QuarantineEntry,
+} from "./Quarantine";
+
+type QuarantineDialogProps = {
+  // Called after demos were removed from the quarantine
+  onChange: () => void;
+  ref: React.RefObject<QuarantineDialog>;
+};
+
+type QuarantineDialogState = {
+  demos: [string, QuarantineEntry][];
+  open: boolean;
+};
+
+export default class QuarantineDialog extends React.Component<
+  QuarantineDialogProps,
+  QuarantineDialogState
+> {
+  constructor(props: QuarantineDialogProps) {
+    super(props);
+    this.state = { demos: [], open: false };
+  }
+
+  open = () => {
+    this.setState({ demos: getQuarantinedDemos(), open: true });
+  };
+
+  close = () => {
+    this.setState({ open: false });
+  };
+
+  clear = (filename?: string) => {
+    const { onChange } = this.props;
+    clearQuarantine(filename);
+    this.setState({ demos: getQuarantinedDemos() });
+    onChange();
+  };
+
+  render() {
+    const { open, demos } = this.state;
+
+    return (
+      <SmallDialog
+        title="Quarantined demos"
+        open={open}
+        onClose={this.close}
+        maxWidth="sm"
+        actions={
+          <>
+            <Button variant="contained" onClick={this.close}>
+              Close
+            </Button>
+            <Button
+              variant="contained"
+              color="primary"
+              disabled={demos.length === 0}
+              onClick={() => this.clear()}
+            >
+              Retry all
+            </Button>
+          </>
+        }
+      >
+        <DialogContentText>
+          These demos could not be read several times in a row and are
+          skipped while loading the demo list.
+        </DialogContentText>
+        <List dense>
+          {demos.length === 0 && (
+            <ListItem>
+              <ListItemText primary="No demos are quarantined." />
+            </ListItem>
+          )}
+          {demos.map(([filename, entry]) => (
+            <ListItem key={filename}>
+              <ListItemText
+                primary={path.basename(filename)}
+                secondary={entry.error}
+              />
+              <ListItemSecondaryAction>
+                <Tooltip title="Retry">
+                  <IconButton edge="end" onClick={() => this.clear(filename)}>
+                    <RefreshIcon />
+                  </IconButton>
+                </Tooltip>
+              </ListItemSecondaryAction>
+            </ListItem>
+          ))}
+        </List>
+      </SmallDialog>
+    );
+  }
+}
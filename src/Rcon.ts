@@ -1,10 +1,18 @@
+import { EventEmitter } from "events";
 import net from "net";
 
+import cfg from "electron-cfg";
 import log from "electron-log";
 
 // See the RCON protocol documentation at
 // https://developer.valvesoftware.com/wiki/Source_RCON_Protocol
 
+// Default timeouts in ms, can be changed with the rcon_connect_timeout
+// and rcon_command_timeout settings. Loading a map can block the game
+// for a while, so these are generous.
+export const DEFAULT_CONNECT_TIMEOUT = 10000;
+export const DEFAULT_COMMAND_TIMEOUT = 30000;
+
 enum RconPacketType {
   SERVERDATA_RESPONSE_VALUE = 0,
   SERVERDATA_EXECCOMMAND = 2,
@@ -19,6 +27,69 @@ type RconCommandCallbacks = {
   onError: RconCommandErrorCallback;
 };
 
+// Messages shown to the user for common error codes
+const ERROR_MESSAGES: Record<string, string> = {
+  ECONNREFUSED:
+    "The game isn't accepting RCON connections. It needs to be started " +
+    "with -usercon.",
+  ECONNRESET: "The connection was closed by the game.",
+  EPASSWD: "The RCON password is wrong.",
+  ETIMEDOUT: "The game didn't respond in time.",
+  ENOTCONNECTED: "Not connected to the game.",
+  ENOSERVER: "There is no saved server with this name.",
+  EDANGEROUS: "The command could shut down the server and wasn't sent.",
+};
+
+export class RconError extends Error {
+  code: string;
+
+  constructor(code: string) {
+    super();
+    this.code = code;
+    this.message = ERROR_MESSAGES[code] ?? `RCON error: ${code}`;
+    this.name = "RconError";
+  }
+}
+
+export class RconTimeoutError extends RconError {
+  constructor() {
+    super("ETIMEDOUT");
+    this.name = "RconTimeoutError";
+  }
+}
+
+export function createRconError(code: string) {
+  return code === "ETIMEDOUT" ? new RconTimeoutError() : new RconError(code);
+}
+
+// Calls onError with ETIMEDOUT if neither callback was called in time
+function withTimeout(
+  callbacks: RconCommandCallbacks,
+  timeout: number
+): RconCommandCallbacks {
+  let done = false;
+  const timer = setTimeout(() => {
+    done = true;
+    callbacks.onError("ETIMEDOUT");
+  }, timeout);
+  return {
+    onSuccess: (response) => {
+      if (!done) {
+        done = true;
+        clearTimeout(timer);
+        callbacks.onSuccess(response);
+      }
+    },
+    onError: (reason) => {
+      if (!done) {
+        done = true;
+        clearTimeout(timer);
+        callbacks.onError(reason);
+      }
+    },
+  };
+}
+
 interface RconPacket {
   id: number;
   type: RconPacketType;
@@ -54,20 +125,33 @@ function readPacket(buffer: Buffer): RconPacket {
   return packet;
 }
 
-function readPackets(buffer: Buffer): RconPacket[] {
+// Returns all complete packets in the buffer and the remaining bytes,
+// since TCP may split a packet across multiple data events.
+function readPackets(buffer: Buffer): [RconPacket[], Buffer] {
   log.debug("[RCON]\tReceived packets:", formatBuffer(buffer));
   let bytesRead = 0;
   const packets: RconPacket[] = [];
-  while (bytesRead < buffer.length) {
+  while (bytesRead + 4 <= buffer.length) {
     const size = buffer.readInt32LE(bytesRead);
+    if (bytesRead + 4 + size > buffer.length) {
+      break;
+    }
     const packet = buffer.slice(bytesRead + 4, bytesRead + 4 + size);
     packets.push(readPacket(packet));
     bytesRead += 4 + size;
   }
-  return packets;
+  return [packets, buffer.slice(bytesRead)];
 }
 
-class RconConnection {
+/**
+ * Emits an "output" event with the packet id and body for every response
+ * packet received, so long responses can be displayed while they arrive.
+ * Emits "connected" after authenticating and "disconnected" when the
+ * connection is closed.
+ */
+export class RconConnection extends EventEmitter {
+  host?: string;
+
   port?: number;
 
   password?: string;
@@ -81,18 +165,42 @@ class RconConnection {
   // Callbacks to call after establishing a connection are at index -1.
   callbacks: Record<number, RconCommandCallbacks> = [];
 
+  // Response bodies of commands that are still being received.
+  // Long responses are split across multiple packets.
+  responses: Record<number, string> = {};
+
+  // Maps the ids of empty response packets sent after each command to the
+  // id of that command. The game mirrors them once it has sent the whole
+  // response, which marks the end of the command's response.
+  terminators: Record<number, number> = {};
+
+  // Received bytes that don't form a complete packet yet
+  receiveBuffer = Buffer.alloc(0);
+
   connected = false;
 
   connect = (
     port: number,
     password: string,
     onSuccess: RconCommandSuccessCallback,
-    onError: RconCommandErrorCallback
+    onError: RconCommandErrorCallback,
+    host = "localhost"
   ) => {
+    this.host = host;
+    this.port = port;
     this.seq = 0;
     this.callbacks = [];
+    this.responses = {};
+    this.terminators = {};
+    this.receiveBuffer = Buffer.alloc(0);
+    this.socket?.destroy();
+    if (this.connected) {
+      this.connected = false;
+      this.emit("disconnected");
+    }
+    let socket: net.Socket;
     try {
-      this.socket = net.createConnection({ port }, () => {
+      socket = net.createConnection({ host, port }, () => {
         log.debug("[RCON]\tAttempting auth");
         this.sendPacket(RconPacketType.SERVERDATA_AUTH, password);
       });
@@ -102,21 +210,89 @@ class RconConnection {
       return;
     }
     log.debug("[RCON]\tSocket created");
-    this.socket.on("data", this.handleData);
-    this.socket.on("close", this.handleClose);
-    this.socket.on("error", this.handleError);
+    this.socket = socket;
+    // Events of a previous socket must not affect the new connection
+    socket.on("data", (data) => {
+      if (socket === this.socket) {
+        this.handleData(data);
+      }
+    });
+    socket.on("close", (hadError) => {
+      if (socket === this.socket) {
+        this.handleClose(hadError);
+      }
+    });
+    socket.on("error", (e: Error & { code: string }) => {
+      if (socket === this.socket) {
+        this.handleError(e);
+      }
+    });
 
-    this.callbacks[-1] = { onSuccess, onError };
+    this.callbacks[-1] = withTimeout(
+      {
+        onSuccess,
+        onError: (reason) => {
+          if (reason === "ETIMEDOUT") {
+            log.debug("[RCON]\tConnection timed out");
+            socket.destroy();
+          }
+          onError(reason);
+        },
+      },
+      cfg.get("rcon_connect_timeout", DEFAULT_CONNECT_TIMEOUT)
+    );
   };
 
-  sendPacket(type: RconPacketType, body: string) {
-    if (this.socket !== undefined) {
-      const packet: RconPacket = { id: this.seq, type, body };
+  disconnect() {
+    this.socket?.destroy();
+  }
+
+  encodePacket(type: RconPacketType, body: string) {
+    const packet: RconPacket = { id: this.seq, type, body };
+    const packetBytes = buildPacket(packet);
+    // The body of auth packets is the password, which must not end up
+    // in the logs users attach to bug reports
+    if (type === RconPacketType.SERVERDATA_AUTH) {
+      log.debug("[RCON]\tSending auth packet");
+    } else {
       log.debug("[RCON]\tSending packet:", packet);
-      const packetBytes = buildPacket(packet);
       log.debug("[RCON]\tEncoded packet:", formatBuffer(packetBytes));
-      this.socket.write(packetBytes);
     }
+    return packetBytes;
+  }
+
+  sendPacket(type: RconPacketType, body: string) {
+    this.socket?.write(this.encodePacket(type, body));
+  }
+
+  // Registers the callbacks of a command and returns the packets to send
+  // for it, without sending them
+  prepareCommand(
+    cmd: string,
+    onSuccess: RconCommandSuccessCallback,
+    onError: RconCommandErrorCallback
+  ) {
+    const id = this.seq;
+    const commandPacket = this.encodePacket(
+      RconPacketType.SERVERDATA_EXECCOMMAND,
+      cmd
+    );
+    // A late response is ignored, since onError was already called
+    this.callbacks[id] = withTimeout(
+      { onError, onSuccess },
+      cfg.get("rcon_command_timeout", DEFAULT_COMMAND_TIMEOUT)
+    );
+    this.responses[id] = "";
+    this.seq += 1;
+    // Packets are processed in order, so the mirrored empty response
+    // arrives after all packets of the actual response.
+    const terminatorPacket = this.encodePacket(
+      RconPacketType.SERVERDATA_RESPONSE_VALUE,
+      ""
+    );
+    this.terminators[this.seq] = id;
+    this.seq += 1;
+    return Buffer.concat([commandPacket, terminatorPacket]);
   }
 
   executeCommand = (
@@ -125,16 +301,66 @@ class RconConnection {
     onError: RconCommandErrorCallback
   ) => {
     if (this.connected) {
-      this.sendPacket(RconPacketType.SERVERDATA_EXECCOMMAND, cmd);
-      this.callbacks[this.seq] = { onError, onSuccess };
-      this.seq += 1;
+      this.socket?.write(this.prepareCommand(cmd, onSuccess, onError));
     } else {
       onError("ENOTCONNECTED");
     }
   };
 
+  // Promise-based variant of executeCommand, rejects with an RconError,
+  // or an RconTimeoutError if the game doesn't respond in time.
+  // onOutput is called with each part of the response as it arrives.
+  execute = (cmd: string, onOutput?: (output: string) => void) =>
+    new Promise<string | undefined>((resolve, reject) => {
+      // executeCommand uses the current sequence number as the id
+      const id = this.seq;
+      const outputListener = (packetId: number, body: string) => {
+        if (packetId === id) {
+          onOutput?.(body);
+        }
+      };
+      if (onOutput !== undefined) {
+        this.on("output", outputListener);
+      }
+      this.executeCommand(
+        cmd,
+        (response) => {
+          this.off("output", outputListener);
+          resolve(response);
+        },
+        (reason) => {
+          this.off("output", outputListener);
+          reject(createRconError(reason));
+        }
+      );
+    });
+
+  // Sends all commands in a single write, so no other command can run
+  // between them. Resolves with the response of each command, in order.
+  executeAll = (cmds: string[]) => {
+    if (!this.connected) {
+      return Promise.reject(createRconError("ENOTCONNECTED"));
+    }
+    const packets: Buffer[] = [];
+    const responses = cmds.map(
+      (cmd) =>
+        new Promise<string | undefined>((resolve, reject) => {
+          packets.push(
+            this.prepareCommand(cmd, resolve, (reason) => {
+              reject(createRconError(reason));
+            })
+          );
+        })
+    );
+    this.socket?.write(Buffer.concat(packets));
+    return Promise.all(responses);
+  };
+
   handleData = (data: Buffer) => {
-    const packets = readPackets(data);
+    const [packets, rest] = readPackets(
+      Buffer.concat([this.receiveBuffer, data])
+    );
+    this.receiveBuffer = rest;
     for (let i = 0; i < packets.length; i += 1) {
       const packet = packets[i];
       let callback: RconCommandCallbacks;
@@ -149,16 +375,27 @@ class RconConnection {
               log.debug("[RCON]\tAuthentication success");
               this.connected = true;
               callback.onSuccess();
+              this.emit("connected");
             }
             delete this.callbacks[-1];
           }
           break;
         case RconPacketType.SERVERDATA_RESPONSE_VALUE:
-          callback = this.callbacks[packet.id];
-          if (callback !== undefined) {
-            callback.onSuccess(packet.body);
-            delete this.callbacks[packet.id];
-          } else {
+          if (packet.id in this.responses) {
+            this.responses[packet.id] += packet.body;
+            this.emit("output", packet.id, packet.body);
+          } else if (packet.id in this.terminators) {
+            const id = this.terminators[packet.id];
+            callback = this.callbacks[id];
+            if (callback !== undefined) {
+              callback.onSuccess(this.responses[id]);
+            }
+            delete this.callbacks[id];
+            delete this.responses[id];
+            delete this.terminators[packet.id];
+          } else if (packet.body !== "\x00\x01\x00\x00") {
+            // The game follows each mirrored empty response with a packet
+            // with this body, it is expected
             log.debug("[RCON]\tReceived unexpected packet", packet);
           }
           break;
@@ -172,7 +409,12 @@ class RconConnection {
   handleClose = (had_error: boolean) => {
     log.debug("[RCON]\tSocket closed, had error:", had_error);
     this.callbacks = {};
-    this.connected = false;
+    this.responses = {};
+    this.terminators = {};
+    if (this.connected) {
+      this.connected = false;
+      this.emit("disconnected");
+    }
   };
 
   handleError = (e: Error & { code: string }) => {
@@ -0,0 +1,64 @@
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import rconConnection from "./Rcon";
+import { executeOnServer } from "./RconServers";
+
+const HISTORY_LENGTH = 100;
+const MAX_COMPLETIONS = 20;
+
+// Names of all convars and commands, loaded on the first completion request
+let cvarNames: string[] | null = null;
+
+export function getCommandHistory(): string[] {
+  return cfg.get("rcon_history", []);
+}
+
+function addToHistory(cmd: string) {
+  const history = getCommandHistory().filter((entry) => entry !== cmd);
+  history.push(cmd);
+  cfg.set("rcon_history", history.slice(-HISTORY_LENGTH));
+}
+
+/**
+ * Runs a command typed into the console and returns its full output.
+ * onOutput is called with each part of long responses as it arrives.
+ * If a server name is given, the command is sent to that server instead
+ * of the game. Dangerous commands are sent too, so the caller has to
+ * check them with isDangerousCommand first.
+ */
+export async function runConsoleCommand(
+  cmd: string,
+  onOutput?: (output: string) => void,
+  server?: string
+) {
+  addToHistory(cmd);
+  if (server !== undefined) {
+    const output = await executeOnServer(server, cmd, true);
+    onOutput?.(output);
+    return output;
+  }
+  return (await rconConnection.execute(cmd, onOutput)) ?? "";
+}
+
+async function loadCvarNames() {
+  log.debug("[RCON]\tLoading cvar list");
+  const cvarlist = (await rconConnection.execute("cvarlist")) ?? "";
+  // Lines look like "name : value : flags : description"
+  return cvarlist
+    .split("\n")
+    .map((line) => /^([\w+-]+)\s+:/.exec(line))
+    .filter((match): match is RegExpExecArray => match !== null)
+    .map((match) => match[1])
+    .sort();
+}
+
+export async function completeCommand(prefix: string) {
+  if (cvarNames === null) {
+    cvarNames = await loadCvarNames();
+  }
+  const lowerCasePrefix = prefix.toLowerCase();
+  return cvarNames
+    .filter((name) => name.toLowerCase().startsWith(lowerCasePrefix))
+    .slice(0, MAX_COMPLETIONS);
+}
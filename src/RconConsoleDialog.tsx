@@ -0,0 +1,275 @@
+import React from "react";
+import { remote } from "electron";
+
+import Button from "@material-ui/core/Button";
+import Grid from "@material-ui/core/Grid";
+import MenuItem from "@material-ui/core/MenuItem";
+import Paper from "@material-ui/core/Paper";
+import TextField from "@material-ui/core/TextField";
+
+import SmallDialog from "./SmallDialog";
+import RconServersDialog from "./RconServersDialog";
+import {
+  completeCommand,
+  getCommandHistory,
+  runConsoleCommand,
+} from "./RconConsole";
+import { getRconServers, isDangerousCommand } from "./RconServers";
+
+// Value of the target selection for the game DemoMan is connected to
+const GAME_TARGET = "";
+
+// Older output is dropped, so long sessions don't slow down the dialog
+const MAX_OUTPUT_LENGTH = 100000;
+
+type RconConsoleDialogProps = {
+  ref: React.RefObject<RconConsoleDialog>;
+};
+
+type RconConsoleDialogState = {
+  open: boolean;
+  output: string;
+  input: string;
+  // Position in the command history while browsing it with the arrow keys
+  historyIndex: number | null;
+  // Name of the server commands are sent to, or GAME_TARGET
+  target: string;
+  serverNames: string[];
+};
+
+function commonPrefix(values: string[]) {
+  return values.reduce((prefix, value) => {
+    let i = 0;
+    while (i < prefix.length && prefix[i] === value[i]) {
+      i += 1;
+    }
+    return prefix.slice(0, i);
+  });
+}
+
+export default class RconConsoleDialog extends React.Component<
+  RconConsoleDialogProps,
+  RconConsoleDialogState
+> {
+  private outputRef = React.createRef<HTMLPreElement>();
+
+  private serversDialog = React.createRef<RconServersDialog>();
+
+  constructor(props: RconConsoleDialogProps) {
+    super(props);
+    this.state = {
+      open: false,
+      output: "",
+      input: "",
+      historyIndex: null,
+      target: GAME_TARGET,
+      serverNames: [],
+    };
+  }
+
+  componentDidUpdate(
+    _prevProps: RconConsoleDialogProps,
+    prevState: RconConsoleDialogState
+  ) {
+    const { output } = this.state;
+    const outputElement = this.outputRef.current;
+    if (output !== prevState.output && outputElement !== null) {
+      outputElement.scrollTop = outputElement.scrollHeight;
+    }
+  }
+
+  open = () => {
+    this.setState({ open: true });
+    this.loadServers();
+  };
+
+  loadServers = () => {
+    const serverNames = getRconServers().map((server) => server.name);
+    this.setState((state) => ({
+      serverNames,
+      // The selected server may have been removed
+      target: serverNames.includes(state.target) ? state.target : GAME_TARGET,
+    }));
+  };
+
+  // Asks before sending commands that could take a server down
+  confirmDangerous = (cmd: string, server: string) => {
+    const choice = remote.dialog.showMessageBoxSync({
+      type: "warning",
+      title: "Send command",
+      message: `"${cmd}" could shut down ${server} or lock you out of it.`,
+      detail: "Do you want to send it anyway?",
+      buttons: ["Cancel", "Send"],
+      defaultId: 0,
+      cancelId: 0,
+    });
+    return choice === 1;
+  };
+
+  close = () => {
+    this.setState({ open: false });
+  };
+
+  print = (text: string) => {
+    this.setState((state) => ({
+      output: (state.output + text).slice(-MAX_OUTPUT_LENGTH),
+    }));
+  };
+
+  run = async () => {
+    const { input, target } = this.state;
+    const cmd = input.trim();
+    this.setState({ input: "", historyIndex: null });
+    if (cmd === "") {
+      return;
+    }
+    const server = target === GAME_TARGET ? undefined : target;
+    if (
+      server !== undefined &&
+      isDangerousCommand(cmd) &&
+      !this.confirmDangerous(cmd, server)
+    ) {
+      return;
+    }
+    this.print(`] ${cmd}\n`);
+    try {
+      await runConsoleCommand(cmd, this.print, server);
+    } catch (e) {
+      this.print(`${e.message}\n`);
+    }
+  };
+
+  complete = async () => {
+    const { input } = this.state;
+    if (input.includes(" ")) {
+      return;
+    }
+    let matches;
+    try {
+      matches = await completeCommand(input);
+    } catch (e) {
+      this.print(`${e.message}\n`);
+      return;
+    }
+    if (matches.length === 1) {
+      this.setState({ input: `${matches[0]} ` });
+    } else if (matches.length > 1) {
+      this.print(`${matches.join("\n")}\n`);
+      this.setState({ input: commonPrefix(matches) });
+    }
+  };
+
+  browseHistory = (offset: number) => {
+    const { historyIndex } = this.state;
+    const history = getCommandHistory();
+    const index = (historyIndex ?? history.length) + offset;
+    if (index < 0) {
+      return;
+    }
+    if (index >= history.length) {
+      this.setState({ input: "", historyIndex: null });
+    } else {
+      this.setState({ input: history[index], historyIndex: index });
+    }
+  };
+
+  keyDown = (event: React.KeyboardEvent) => {
+    if (event.key === "Enter") {
+      this.run();
+    } else if (event.key === "Tab") {
+      event.preventDefault();
+      this.complete();
+    } else if (event.key === "ArrowUp") {
+      event.preventDefault();
+      this.browseHistory(-1);
+    } else if (event.key === "ArrowDown") {
+      event.preventDefault();
+      this.browseHistory(1);
+    }
+  };
+
+  render() {
+    const { open, output, input, target, serverNames } = this.state;
+    return (
+      <SmallDialog
+        title="Console"
+        open={open}
+        onClose={this.close}
+        maxWidth="md"
+        actions={
+          <>
+            <Button
+              variant="contained"
+              onClick={() => this.setState({ output: "" })}
+            >
+              Clear
+            </Button>
+            <Button variant="contained" onClick={this.close}>
+              Close
+            </Button>
+          </>
+        }
+      >
+        <Grid container spacing={1} alignItems="center">
+          <Grid item xs>
+            <TextField
+              select
+              label="Send commands to"
+              value={target}
+              onChange={(event) => {
+                this.setState({ target: event.target.value });
+              }}
+              fullWidth
+            >
+              <MenuItem value={GAME_TARGET}>TF2</MenuItem>
+              {serverNames.map((name) => (
+                <MenuItem key={name} value={name}>
+                  {name}
+                </MenuItem>
+              ))}
+            </TextField>
+          </Grid>
+          <Grid item>
+            <Button
+              variant="outlined"
+              onClick={() => this.serversDialog.current?.open()}
+            >
+              Servers...
+            </Button>
+          </Grid>
+        </Grid>
+        <Paper variant="outlined" style={{ marginTop: "8px" }}>
+          <pre
+            ref={this.outputRef}
+            style={{
+              height: "400px",
+              margin: 0,
+              padding: "8px",
+              overflowY: "auto",
+              whiteSpace: "pre-wrap",
+              wordBreak: "break-all",
+            }}
+          >
+            {output}
+          </pre>
+        </Paper>
+        <TextField
+          placeholder="Command, Tab completes, arrow keys browse the history"
+          value={input}
+          onChange={(event) => {
+            this.setState({ input: event.target.value, historyIndex: null });
+          }}
+          onKeyDown={this.keyDown}
+          spellCheck={false}
+          autoFocus
+          fullWidth
+          margin="normal"
+        />
+        <RconServersDialog
+          ref={this.serversDialog}
+          onChange={this.loadServers}
+        />
+      </SmallDialog>
+    );
+  }
+}
@@ -0,0 +1,124 @@
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import { createRconError, RconConnection, RconError } from "./Rcon";
+import {
+  clearCredential,
+  getCredential,
+  getRconServerCredentialName,
+  setCredential,
+} from "./Credentials";
+
+// The password is kept in the credential store
+export interface RconServer {
+  name: string;
+  host: string;
+  port: number;
+}
+
+// Commands that shut down or lock out a server. They are only sent to
+// remote servers when explicitly allowed.
+const DANGEROUS_COMMANDS = [
+  "quit",
+  "exit",
+  "_restart",
+  "sv_shutdown",
+  "rcon_password",
+  "sv_password",
+  "killserver",
+];
+
+// Default number of additional connection attempts, can be changed
+// with the rcon_connect_retries setting
+export const DEFAULT_CONNECT_RETRIES = 2;
+
+const connections = new Map<string, RconConnection>();
+
+export function getRconServers(): RconServer[] {
+  return cfg.get("rcon_servers", []);
+}
+
+export async function saveRconServer(server: RconServer, password: string) {
+  // An open connection would keep using the old host and password
+  connections.get(server.name)?.socket?.destroy();
+  connections.delete(server.name);
+  await setCredential(getRconServerCredentialName(server.name), password);
+  cfg.set("rcon_servers", [
+    ...getRconServers().filter((entry) => entry.name !== server.name),
+    server,
+  ]);
+}
+
+export async function removeRconServer(name: string) {
+  connections.get(name)?.socket?.destroy();
+  connections.delete(name);
+  cfg.set(
+    "rcon_servers",
+    getRconServers().filter((entry) => entry.name !== name)
+  );
+  await clearCredential(getRconServerCredentialName(name));
+}
+
+// Checks every command of a chain like "status; quit". Like ";", line
+// breaks separate commands in the Source console.
+export function isDangerousCommand(cmd: string) {
+  return cmd
+    .split(/[;\r\n]/)
+    // The console also accepts quoted command names
+    .map((part) => part.trim().split(/\s+/)[0].replace(/"/g, "").toLowerCase())
+    .some((name) => DANGEROUS_COMMANDS.includes(name));
+}
+
+async function getConnection(name: string) {
+  const existing = connections.get(name);
+  if (existing?.connected) {
+    return existing;
+  }
+  const server = getRconServers().find((entry) => entry.name === name);
+  if (server === undefined) {
+    throw new RconError("ENOSERVER");
+  }
+  const password = await getCredential(getRconServerCredentialName(name));
+  const connection = existing ?? new RconConnection();
+  const retries = cfg.get("rcon_connect_retries", DEFAULT_CONNECT_RETRIES);
+  for (let attempt = 0; ; attempt += 1) {
+    log.info(`[RCON]\tConnecting to ${server.name} (${server.host})`);
+    try {
+      // eslint-disable-next-line no-await-in-loop
+      await new Promise<void>((resolve, reject) => {
+        connection.connect(
+          server.port,
+          password,
+          () => resolve(),
+          (reason) => reject(createRconError(reason)),
+          server.host
+        );
+      });
+      connections.set(name, connection);
+      return connection;
+    } catch (e) {
+      // A wrong password won't fix itself by retrying
+      if (e.code === "EPASSWD" || attempt >= retries) {
+        throw e;
+      }
+    }
+  }
+}
+
+/**
+ * Runs the command on the named server, connecting first if needed.
+ * Rejects with the code EDANGEROUS for commands that could take the
+ * server down, unless allowDangerous is set.
+ */
+export async function executeOnServer(
+  name: string,
+  cmd: string,
+  allowDangerous = false
+) {
+  if (!allowDangerous && isDangerousCommand(cmd)) {
+    log.warn(`[RCON]\tRefusing to send "${cmd}" to ${name}`);
+    throw new RconError("EDANGEROUS");
+  }
+  const connection = await getConnection(name);
+  return (await connection.execute(cmd)) ?? "";
+}
@@ -0,0 +1,190 @@
+import React from "react";
+
+import Button from "@material-ui/core/Button";
+import DialogContentText from "@material-ui/core/DialogContentText";
+import Grid from "@material-ui/core/Grid";
+import IconButton from "@material-ui/core/IconButton";
+import List from "@material-ui/core/List";
+import ListItem from "@material-ui/core/ListItem";
+import ListItemSecondaryAction from "@material-ui/core/ListItemSecondaryAction";
+import ListItemText from "@material-ui/core/ListItemText";
+import TextField from "@material-ui/core/TextField";
+import Tooltip from "@material-ui/core/Tooltip";
+import DeleteIcon from "@material-ui/icons/Delete";
+
+import SmallDialog from "./SmallDialog";
+import {
+  getRconServers,
+  removeRconServer,
+  RconServer,
+  saveRconServer,
+} from "./RconServers";
+
+const DEFAULT_PORT = "27015";
+
+type RconServersDialogProps = {
+  // Called after a server was added or removed
+  onChange: () => void;
+  ref: React.RefObject<RconServersDialog>;
+};
+
+type RconServersDialogState = {
+  open: boolean;
+  servers: RconServer[];
+  name: string;
+  host: string;
+  port: string;
+  password: string;
+};
+
+function isValidPort(port: string) {
+  return /^\d+$/.test(port) && Number(port) > 0 && Number(port) <= 65535;
+}
+
+/**
+ * Manages the game servers that commands can be sent to from the console.
+ * Saving a server with an existing name replaces it.
+ */
+export default class RconServersDialog extends React.Component<
+  RconServersDialogProps,
+  RconServersDialogState
+> {
+  constructor(props: RconServersDialogProps) {
+    super(props);
+    this.state = {
+      open: false,
+      servers: [],
+      name: "",
+      host: "",
+      port: DEFAULT_PORT,
+      password: "",
+    };
+  }
+
+  open = () => {
+    this.setState({ open: true, servers: getRconServers() });
+  };
+
+  close = () => {
+    this.setState({ open: false });
+  };
+
+  addServer = async () => {
+    const { onChange } = this.props;
+    const { name, host, port, password } = this.state;
+    await saveRconServer(
+      { name: name.trim(), host: host.trim(), port: Number(port) },
+      password
+    );
+    this.setState({
+      servers: getRconServers(),
+      name: "",
+      host: "",
+      port: DEFAULT_PORT,
+      password: "",
+    });
+    onChange();
+  };
+
+  removeServer = async (name: string) => {
+    const { onChange } = this.props;
+    await removeRconServer(name);
+    this.setState({ servers: getRconServers() });
+    onChange();
+  };
+
+  render() {
+    const { open, servers, name, host, port, password } = this.state;
+    return (
+      <SmallDialog
+        title="RCON servers"
+        open={open}
+        onClose={this.close}
+        maxWidth="sm"
+        actions={
+          <Button variant="contained" onClick={this.close}>
+            Close
+          </Button>
+        }
+      >
+        {servers.length === 0 ? (
+          <DialogContentText>
+            Add a server to send commands to it from the console.
+          </DialogContentText>
+        ) : (
+          <List dense>
+            {servers.map((server) => (
+              <ListItem key={server.name}>
+                <ListItemText
+                  primary={server.name}
+                  secondary={`${server.host}:${server.port}`}
+                />
+                <ListItemSecondaryAction>
+                  <Tooltip title="Remove">
+                    <IconButton
+                      edge="end"
+                      onClick={() => this.removeServer(server.name)}
+                    >
+                      <DeleteIcon />
+                    </IconButton>
+                  </Tooltip>
+                </ListItemSecondaryAction>
+              </ListItem>
+            ))}
+          </List>
+        )}
+        <Grid container spacing={1} alignItems="flex-end">
+          <Grid item xs={6}>
+            <TextField
+              label="Name"
+              value={name}
+              onChange={(event) => this.setState({ name: event.target.value })}
+              fullWidth
+            />
+          </Grid>
+          <Grid item xs={6}>
+            <TextField
+              label="Password"
+              type="password"
+              value={password}
+              onChange={(event) => {
+                this.setState({ password: event.target.value });
+              }}
+              fullWidth
+            />
+          </Grid>
+          <Grid item xs={6}>
+            <TextField
+              label="Host"
+              value={host}
+              onChange={(event) => this.setState({ host: event.target.value })}
+              spellCheck={false}
+              fullWidth
+            />
+          </Grid>
+          <Grid item xs={3}>
+            <TextField
+              label="Port"
+              value={port}
+              error={!isValidPort(port)}
+              onChange={(event) => this.setState({ port: event.target.value })}
+              fullWidth
+            />
+          </Grid>
+          <Grid item xs={3}>
+            <Button
+              variant="contained"
+              color="primary"
+              disabled={
+                name.trim() === "" || host.trim() === "" || !isValidPort(port)
+              }
+              onClick={this.addServer}
+            >
+              Save
+            </Button>
+          </Grid>
+        </Grid>
+      </SmallDialog>
+    );
+  }
+}
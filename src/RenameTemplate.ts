@@ -0,0 +1,98 @@
+import fs from "fs";
+import path from "path";
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import { Demo } from "./Demos";
+
+export const DEFAULT_RENAME_TEMPLATE = "{date}_{map}";
+
+export function getRenameTemplate(): string {
+  return cfg.get("rename_template", DEFAULT_RENAME_TEMPLATE);
+}
+
+// Replaces characters that aren't allowed in demo names, see RenameDialog
+function sanitize(value: string) {
+  return value.replace(/[^a-zA-Z0-9\-_ [\]().]/g, "_");
+}
+
+function formatDate(timestamp: number) {
+  const date = new Date(timestamp);
+  const month = `${date.getMonth() + 1}`.padStart(2, "0");
+  const day = `${date.getDate()}`.padStart(2, "0");
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+function formatTime(timestamp: number) {
+  const date = new Date(timestamp);
+  const hours = `${date.getHours()}`.padStart(2, "0");
+  const minutes = `${date.getMinutes()}`.padStart(2, "0");
+  return `${hours}-${minutes}`;
+}
+
+/**
+ * Fills in the placeholders {map}, {date}, {time}, {server} and {player}
+ * of the template with the demo's header data.
+ */
+export function formatDemoName(demo: Demo, template: string) {
+  const values: Record<string, string> = {
+    map: demo.header.mapName,
+    date: formatDate(demo.birthtime),
+    time: formatTime(demo.birthtime),
+    server: demo.header.serverName,
+    player: demo.header.clientName,
+  };
+  return sanitize(
+    template.replace(/\{(\w+)\}/g, (match, key: string) =>
+      key in values ? values[key] : match
+    )
+  ).trim();
+}
+
+// Appends a number to the name if another demo or events file has it
+function getAvailableName(demo: Demo, name: string) {
+  const dir = path.dirname(demo.filename);
+  let candidate = name;
+  for (let i = 2; ; i += 1) {
+    if (
+      candidate === demo.getShortName() ||
+      (!fs.existsSync(path.join(dir, `${candidate}.dem`)) &&
+        !fs.existsSync(path.join(dir, `${candidate}.json`)))
+    ) {
+      return candidate;
+    }
+    candidate = `${name} (${i})`;
+  }
+}
+
+/**
+ * Renames the demo and its events file according to the template.
+ * Returns false if the demo already has the resulting name.
+ */
+export function renameDemoByTemplate(demo: Demo, template: string) {
+  const formattedName = formatDemoName(demo, template);
+  if (formattedName === "") {
+    return false;
+  }
+  const newName = getAvailableName(demo, formattedName);
+  if (newName === demo.getShortName()) {
+    return false;
+  }
+  demo.rename(newName);
+  return true;
+}
+
+// Returns the number of demos that were renamed
+export function renameDemosByTemplate(demos: Demo[], template: string) {
+  let renamed = 0;
+  demos.forEach((demo) => {
+    try {
+      if (renameDemoByTemplate(demo, template)) {
+        renamed += 1;
+      }
+    } catch (e) {
+      log.error(`Error renaming demo ${demo.filename}: ${e}`);
+    }
+  });
+  return renamed;
+}
@@ -0,0 +1,78 @@
+import rconConnection from "./Rcon";
+import { steamId3To64 } from "./util";
+
+export interface StatusPlayer {
+  userId: number;
+  name: string;
+  // undefined for bots
+  steamId?: string;
+  steamId64?: string;
+  connected?: string;
+  ping?: number;
+  loss?: number;
+  state: string;
+  address?: string;
+}
+
+export interface ServerStatus {
+  hostname?: string;
+  map?: string;
+  maxPlayers?: number;
+  players: StatusPlayer[];
+}
+
+// # userid name uniqueid connected ping loss state adr
+const playerRegex =
+  /^#\s*(\d+)\s+"(.*)"\s+(\[U:\d:\d+\])\s+(\S+)\s+(\d+)\s+(\d+)\s+(\w+)(?:\s+(\S+))?/;
+// # userid name BOT state
+const botRegex = /^#\s*(\d+)\s+"(.*)"\s+BOT\s+(\w+)/;
+
+export function parseStatus(output: string): ServerStatus {
+  const status: ServerStatus = { players: [] };
+  output.split("\n").forEach((rawLine) => {
+    const line = rawLine.trim();
+    let match = /^(\w+)\s*:\s*(.*)$/.exec(line);
+    if (match !== null) {
+      const [, key, value] = match;
+      if (key === "hostname") {
+        status.hostname = value;
+      } else if (key === "map") {
+        [status.map] = value.split(" ", 1);
+      } else if (key === "players") {
+        const maxPlayers = /\((\d+) max\)/.exec(value);
+        if (maxPlayers !== null) {
+          status.maxPlayers = parseInt(maxPlayers[1], 10);
+        }
+      }
+      return;
+    }
+    match = playerRegex.exec(line);
+    if (match !== null) {
+      status.players.push({
+        userId: parseInt(match[1], 10),
+        name: match[2],
+        steamId: match[3],
+        steamId64: steamId3To64(match[3]),
+        connected: match[4],
+        ping: parseInt(match[5], 10),
+        loss: parseInt(match[6], 10),
+        state: match[7],
+        address: match[8],
+      });
+      return;
+    }
+    match = botRegex.exec(line);
+    if (match !== null) {
+      status.players.push({
+        userId: parseInt(match[1], 10),
+        name: match[2],
+        state: match[3],
+      });
+    }
+  });
+  return status;
+}
+
+export async function getServerStatus() {
+  return parseStatus((await rconConnection.execute("status")) ?? "");
+}
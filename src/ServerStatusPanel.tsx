@@ -0,0 +1,217 @@
+import React from "react";
+import { shell } from "electron";
+import log from "electron-log";
+
+import Avatar from "@material-ui/core/Avatar";
+import Grid from "@material-ui/core/Grid";
+import IconButton from "@material-ui/core/IconButton";
+import List from "@material-ui/core/List";
+import ListItem from "@material-ui/core/ListItem";
+import ListItemAvatar from "@material-ui/core/ListItemAvatar";
+import ListItemText from "@material-ui/core/ListItemText";
+import Menu from "@material-ui/core/Menu";
+import MenuItem from "@material-ui/core/MenuItem";
+import Tooltip from "@material-ui/core/Tooltip";
+import Typography from "@material-ui/core/Typography";
+import RefreshIcon from "@material-ui/icons/Refresh";
+
+import { getServerStatus, ServerStatus, StatusPlayer } from "./ServerStatus";
+import {
+  getFriendIds,
+  getPlayerBans,
+  getPlayerProfiles,
+  PlayerBans,
+  PlayerProfile,
+} from "./SteamApi";
+import { getPlayerLinks } from "./util";
+
+const LINK_NAMES = {
+  steam: "Steam profile",
+  trendsTf: "trends.tf",
+  logsTf: "logs.tf",
+  demosTf: "demos.tf",
+};
+
+type ServerStatusPanelState = {
+  status: ServerStatus | null;
+  // Steam profiles by SteamID64, only loaded with a Steam Web API key
+  profiles: Record<string, PlayerProfile>;
+  friendIds: Set<string>;
+  bans: Record<string, PlayerBans>;
+  error: string | null;
+  // Player whose profile links are shown in the menu
+  linksMenu: { anchor: HTMLElement; steamId64: string } | null;
+};
+
+function formatBans(bans: PlayerBans) {
+  const types: string[] = [];
+  if (bans.vacBans > 0) {
+    types.push(`${bans.vacBans} VAC`);
+  }
+  if (bans.gameBans > 0) {
+    types.push(`${bans.gameBans} game`);
+  }
+  return (
+    `${types.join(" and ")} ban${bans.vacBans + bans.gameBans > 1 ? "s" : ""}` +
+    `, last one ${bans.daysSinceLastBan} days ago`
+  );
+}
+
+function formatPlayerDetails(
+  player: StatusPlayer,
+  isFriend: boolean,
+  bans?: PlayerBans
+) {
+  if (player.steamId === undefined) {
+    return "Bot";
+  }
+  const details = [player.steamId, `${player.ping} ms`];
+  if (isFriend) {
+    details.unshift("Friend");
+  }
+  if (bans !== undefined) {
+    details.push(formatBans(bans));
+  }
+  return details.join(" · ");
+}
+
+/**
+ * Shows the server the connected game is on and its players,
+ * as reported by the status command.
+ */
+export default class ServerStatusPanel extends React.Component<
+  Record<string, never>,
+  ServerStatusPanelState
+> {
+  constructor(props: Record<string, never>) {
+    super(props);
+    this.state = {
+      status: null,
+      profiles: {},
+      friendIds: new Set(),
+      bans: {},
+      error: null,
+      linksMenu: null,
+    };
+  }
+
+  componentDidMount() {
+    this.refresh();
+  }
+
+  refresh = async () => {
+    try {
+      const status = await getServerStatus();
+      this.setState({ status, error: null });
+      this.loadProfiles(status);
+    } catch (e) {
+      log.warn(`Error getting server status: ${e}`);
+      this.setState({ error: e.message });
+    }
+  };
+
+  loadProfiles = async (status: ServerStatus) => {
+    const steamIds = status.players
+      .map((player) => player.steamId64)
+      .filter((id): id is string => id !== undefined);
+    try {
+      const [profiles, friendIds, bans] = await Promise.all([
+        getPlayerProfiles(steamIds),
+        getFriendIds(),
+        getPlayerBans(steamIds),
+      ]);
+      this.setState({ profiles, friendIds, bans });
+    } catch (e) {
+      log.warn(`Error loading Steam profiles: ${e}`);
+    }
+  };
+
+  render() {
+    const { status, profiles, friendIds, bans, error, linksMenu } =
+      this.state;
+    return (
+      <>
+        <Grid container alignItems="center" justify="space-between">
+          <Grid item>
+            <Typography variant="subtitle1">
+              {status?.hostname ?? "Not on a server"}
+            </Typography>
+            {status?.map !== undefined && (
+              <Typography variant="body2" color="textSecondary">
+                {status.map} · {status.players.length}
+                {status.maxPlayers !== undefined && ` / ${status.maxPlayers}`}
+                &nbsp;players
+              </Typography>
+            )}
+          </Grid>
+          <Grid item>
+            <Tooltip title="Refresh">
+              <IconButton onClick={this.refresh}>
+                <RefreshIcon />
+              </IconButton>
+            </Tooltip>
+          </Grid>
+        </Grid>
+        {error !== null && <Typography color="error">{error}</Typography>}
+        <List dense style={{ maxHeight: "300px", overflowY: "auto" }}>
+          {status?.players.map((player) => {
+            const { steamId64 } = player;
+            const profile =
+              steamId64 === undefined ? undefined : profiles[steamId64];
+            return (
+              <ListItem
+                key={player.userId}
+                button
+                disabled={steamId64 === undefined}
+                onClick={(event: React.MouseEvent<HTMLElement>) => {
+                  if (steamId64 !== undefined) {
+                    this.setState({
+                      linksMenu: { anchor: event.currentTarget, steamId64 },
+                    });
+                  }
+                }}
+              >
+                <ListItemAvatar>
+                  <Avatar src={profile?.avatarUrl}>{player.name[0]}</Avatar>
+                </ListItemAvatar>
+                <ListItemText
+                  primary={
+                    profile === undefined ||
+                    profile.personaName === player.name
+                      ? player.name
+                      : `${player.name} (${profile.personaName})`
+                  }
+                  secondary={formatPlayerDetails(
+                    player,
+                    steamId64 !== undefined && friendIds.has(steamId64),
+                    steamId64 === undefined ? undefined : bans[steamId64]
+                  )}
+                />
+              </ListItem>
+            );
+          })}
+        </List>
+        <Menu
+          anchorEl={linksMenu?.anchor}
+          open={linksMenu !== null}
+          onClose={() => this.setState({ linksMenu: null })}
+        >
+          {linksMenu !== null &&
+            Object.entries(getPlayerLinks(linksMenu.steamId64)).map(
+              ([key, url]) => (
+                <MenuItem
+                  key={key}
+                  onClick={() => {
+                    shell.openExternal(url);
+                    this.setState({ linksMenu: null });
+                  }}
+                >
+                  {LINK_NAMES[key as keyof typeof LINK_NAMES]}
+                </MenuItem>
+              )
+            )}
+        </Menu>
+      </>
+    );
+  }
+}
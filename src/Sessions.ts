@@ -0,0 +1,50 @@
+import { Demo } from "./Demos";
+
+// Maximum time between the end of one demo and the start of the next
+// for both to belong to the same session
+const MAX_SESSION_GAP = 2 * 60 * 60 * 1000;
+
+export interface Session {
+  start: number;
+  demoCount: number;
+}
+
+function isSameDay(a: number, b: number) {
+  return new Date(a).toDateString() === new Date(b).toDateString();
+}
+
+/**
+ * Groups demos into play sessions: demos recorded on the same day
+ * with less than two hours between them.
+ */
+export function groupIntoSessions(demos: Demo[]) {
+  const sessions = new Map<Demo, Session>();
+  const sorted = [...demos].sort((a, b) => a.birthtime - b.birthtime);
+  let session: Session | undefined;
+  let sessionEnd = 0;
+
+  sorted.forEach((demo) => {
+    if (
+      session === undefined ||
+      demo.birthtime - sessionEnd > MAX_SESSION_GAP ||
+      !isSameDay(session.start, demo.birthtime)
+    ) {
+      session = { start: demo.birthtime, demoCount: 0 };
+    }
+    session.demoCount += 1;
+    sessionEnd = Math.max(
+      sessionEnd,
+      demo.birthtime + demo.header.playbackTime * 1000
+    );
+    sessions.set(demo, session);
+  });
+  return sessions;
+}
+
+export function getSessionName(session: Session) {
+  const date = new Date(session.start);
+  const weekday = date.toLocaleDateString(undefined, { weekday: "long" });
+  const { demoCount } = session;
+  const count = `${demoCount} ${demoCount === 1 ? "demo" : "demos"}`;
+  return `${weekday} ${date.toLocaleDateString()} (${count})`;
+}
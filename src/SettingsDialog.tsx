@@ -1,5 +1,5 @@
 import React from "react";
-import { ipcRenderer } from "electron";
+import { ipcRenderer, remote } from "electron";
 import cfg from "electron-cfg";
 import log from "electron-log";
 
@@ -15,9 +15,25 @@ import PaletteIcon from "@material-ui/icons/Palette";
 import DarkThemeIcon from "@material-ui/icons/Brightness3";
 import LightThemeIcon from "@material-ui/icons/Brightness7";
 import SystemThemeIcon from "@material-ui/icons/SettingsApplications";
+import ChatIcon from "@material-ui/icons/Chat";
+import EditIcon from "@material-ui/icons/Edit";
+import CloudUploadIcon from "@material-ui/icons/CloudUpload";
+import ImageIcon from "@material-ui/icons/Image";
+import PeopleIcon from "@material-ui/icons/People";
+import ShareIcon from "@material-ui/icons/Share";
+import SettingsRemoteIcon from "@material-ui/icons/SettingsRemote";
+import ListItemSecondaryAction from "@material-ui/core/ListItemSecondaryAction";
+import Switch from "@material-ui/core/Switch";
+import TextField from "@material-ui/core/TextField";
 
 import { GetDemoPath } from "./GetDemoPath";
 import SmallDialog from "./SmallDialog";
+import { getRenameTemplate } from "./RenameTemplate";
+import { DEFAULT_PASTE_URL } from "./Paste";
+import { clearPresence } from "./DiscordRpc";
+import { DEFAULT_COMMAND_TIMEOUT, DEFAULT_CONNECT_TIMEOUT } from "./Rcon";
+import { DEFAULT_CONNECT_RETRIES } from "./RconServers";
+import { getCredential, setCredential } from "./Credentials";
 
 type SettingsViewState = {
   open: boolean;
@@ -25,10 +41,42 @@ type SettingsViewState = {
   settings: {
     theme: string;
     demo_path: string;
+    discord_rich_presence: boolean;
+    auto_rename_demos: boolean;
+    demostf_api_key: string;
+    steam_api_key: string;
+    map_thumbnail_url: string;
+    gist_token: string;
+    paste_url: string;
+    discord_client_id: string;
+    rcon_connect_timeout: number;
+    rcon_command_timeout: number;
+    rcon_connect_retries: number;
   };
   settingsChanged: boolean;
 };
 
+type TextSetting =
+  | "demostf_api_key"
+  | "steam_api_key"
+  | "map_thumbnail_url"
+  | "gist_token"
+  | "paste_url"
+  | "discord_client_id";
+
+// Secrets are kept in the credential store instead of the settings file
+const CREDENTIAL_SETTINGS: TextSetting[] = [
+  "demostf_api_key",
+  "steam_api_key",
+  "gist_token",
+];
+
+// Timeouts are stored in milliseconds, but shown in seconds
+type NumberSetting =
+  | "rcon_connect_timeout"
+  | "rcon_command_timeout"
+  | "rcon_connect_retries";
+
 const ThemeNames: { [key: string]: string } = {
   dark: "Dark",
   light: "Light",
@@ -55,6 +103,17 @@ export default class SettingsDialog extends React.Component<
         // the settings dialog is opened.
         theme: "",
         demo_path: "",
+        discord_rich_presence: false,
+        auto_rename_demos: false,
+        demostf_api_key: "",
+        steam_api_key: "",
+        map_thumbnail_url: "",
+        gist_token: "",
+        paste_url: "",
+        discord_client_id: "",
+        rcon_connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+        rcon_command_timeout: DEFAULT_COMMAND_TIMEOUT,
+        rcon_connect_retries: DEFAULT_CONNECT_RETRIES,
       },
       settingsChanged: false,
       themePickerAnchor: null,
@@ -67,15 +126,76 @@ export default class SettingsDialog extends React.Component<
       settings: {
         theme: cfg.get("theme"),
         demo_path: cfg.get("demo_path"),
+        discord_rich_presence: cfg.get("discord_rich_presence", false),
+        auto_rename_demos: cfg.get("auto_rename_demos", false),
+        // Loaded from the credential store below
+        demostf_api_key: "",
+        steam_api_key: "",
+        map_thumbnail_url: cfg.get("map_thumbnail_url", ""),
+        gist_token: "",
+        paste_url: cfg.get("paste_url", ""),
+        discord_client_id: cfg.get("discord_client_id", ""),
+        rcon_connect_timeout: cfg.get(
+          "rcon_connect_timeout",
+          DEFAULT_CONNECT_TIMEOUT
+        ),
+        rcon_command_timeout: cfg.get(
+          "rcon_command_timeout",
+          DEFAULT_COMMAND_TIMEOUT
+        ),
+        rcon_connect_retries: cfg.get(
+          "rcon_connect_retries",
+          DEFAULT_CONNECT_RETRIES
+        ),
       },
       settingsChanged: false,
     });
+    this.loadCredentials();
   };
 
   close = () => {
     this.setState({ open: false });
   };
 
+  loadCredentials = async () => {
+    const [demostfApiKey, steamApiKey, gistToken] = await Promise.all(
+      CREDENTIAL_SETTINGS.map((key) => getCredential(key))
+    );
+    this.setState((state) => ({
+      settings: {
+        ...state.settings,
+        demostf_api_key: demostfApiKey,
+        steam_api_key: steamApiKey,
+        gist_token: gistToken,
+      },
+    }));
+  };
+
+  saveSettings = async () => {
+    const { settings } = this.state;
+    const otherSettings: Record<string, unknown> = { ...settings };
+    CREDENTIAL_SETTINGS.forEach((key) => {
+      delete otherSettings[key];
+    });
+    log.debug(`Applying settings: ${JSON.stringify(otherSettings)}`);
+    cfg.setAll(otherSettings);
+    try {
+      await Promise.all(
+        CREDENTIAL_SETTINGS.map((key) => setCredential(key, settings[key]))
+      );
+    } catch (e) {
+      log.error(`Error saving credentials: ${e}`);
+      remote.dialog.showErrorBox("Could not save API keys", e.message);
+    }
+    // The window is reloaded before the connection would
+    // notice that Rich Presence was turned off
+    if (!settings.discord_rich_presence) {
+      await clearPresence();
+    }
+    ipcRenderer.send("update-theme", settings.theme);
+    window.location.reload();
+  };
+
   selectTheme = (newTheme: string) => {
     const { settings } = this.state;
     if (newTheme !== settings.theme) {
@@ -94,6 +214,26 @@ export default class SettingsDialog extends React.Component<
     }
   };
 
+  textSettingChanged = (key: TextSetting, value: string) => {
+    const { settings } = this.state;
+    this.setState({
+      settings: { ...settings, [key]: value.trim() },
+      settingsChanged: true,
+    });
+  };
+
+  // Ignores values that can't be used, like empty fields or a timeout of 0
+  numberSettingChanged = (key: NumberSetting, value: number, min: number) => {
+    const { settings } = this.state;
+    if (!Number.isInteger(value) || value < min) {
+      return;
+    }
+    this.setState({
+      settings: { ...settings, [key]: value },
+      settingsChanged: true,
+    });
+  };
+
   render() {
     const { open, themePickerAnchor, settings, settingsChanged } = this.state;
     return (
@@ -112,12 +252,7 @@ export default class SettingsDialog extends React.Component<
                 variant="contained"
                 color="primary"
                 disabled={!settingsChanged}
-                onClick={() => {
-                  log.debug(`Applying settings: ${settings}`);
-                  cfg.setAll(settings);
-                  ipcRenderer.send("update-theme", settings.theme);
-                  window.location.reload();
-                }}
+                onClick={this.saveSettings}
               >
                 Save changes
               </Button>
@@ -142,6 +277,7 @@ export default class SettingsDialog extends React.Component<
             </ListItem>
             <ListItem
               button
+              divider
               onClick={() => {
                 const newPath = GetDemoPath(settings.demo_path);
                 if (newPath !== undefined && newPath !== settings.demo_path) {
@@ -163,6 +299,210 @@ export default class SettingsDialog extends React.Component<
                 secondary={settings.demo_path}
               />
             </ListItem>
+            <ListItem divider={!settings.discord_rich_presence}>
+              <ListItemIcon>
+                <ChatIcon />
+              </ListItemIcon>
+              <ListItemText
+                primary="Discord Rich Presence"
+                secondary="Show what you're doing in DemoMan on Discord"
+              />
+              <ListItemSecondaryAction>
+                <Switch
+                  edge="end"
+                  checked={settings.discord_rich_presence}
+                  onChange={(event: React.ChangeEvent<HTMLInputElement>) => {
+                    this.setState({
+                      settings: {
+                        ...settings,
+                        discord_rich_presence: event.target.checked,
+                      },
+                      settingsChanged: true,
+                    });
+                  }}
+                />
+              </ListItemSecondaryAction>
+            </ListItem>
+            {settings.discord_rich_presence && (
+              <ListItem divider>
+                <TextField
+                  label="Discord application ID"
+                  helperText="Create one at discord.com/developers"
+                  value={settings.discord_client_id}
+                  onChange={(event) => {
+                    this.textSettingChanged(
+                      "discord_client_id",
+                      event.target.value
+                    );
+                  }}
+                  error={settings.discord_client_id === ""}
+                  spellCheck={false}
+                  fullWidth
+                />
+              </ListItem>
+            )}
+            <ListItem divider>
+              <ListItemIcon>
+                <EditIcon />
+              </ListItemIcon>
+              <ListItemText
+                primary="Rename new recordings"
+                secondary={`Rename finished recordings using the template "${getRenameTemplate()}"`}
+              />
+              <ListItemSecondaryAction>
+                <Switch
+                  edge="end"
+                  checked={settings.auto_rename_demos}
+                  onChange={(event: React.ChangeEvent<HTMLInputElement>) => {
+                    this.setState({
+                      settings: {
+                        ...settings,
+                        auto_rename_demos: event.target.checked,
+                      },
+                      settingsChanged: true,
+                    });
+                  }}
+                />
+              </ListItemSecondaryAction>
+            </ListItem>
+            <ListItem divider>
+              <ListItemIcon>
+                <CloudUploadIcon />
+              </ListItemIcon>
+              <TextField
+                label="demos.tf API key"
+                helperText="Needed for uploads, get yours at demos.tf/upload"
+                type="password"
+                value={settings.demostf_api_key}
+                onChange={(event) => {
+                  this.textSettingChanged(
+                    "demostf_api_key",
+                    event.target.value
+                  );
+                }}
+                spellCheck={false}
+                fullWidth
+              />
+            </ListItem>
+            <ListItem divider>
+              <ListItemIcon>
+                <PeopleIcon />
+              </ListItemIcon>
+              <TextField
+                label="Steam Web API key"
+                helperText="For player avatars, get yours at steamcommunity.com/dev"
+                type="password"
+                value={settings.steam_api_key}
+                onChange={(event) => {
+                  this.textSettingChanged("steam_api_key", event.target.value);
+                }}
+                spellCheck={false}
+                fullWidth
+              />
+            </ListItem>
+            <ListItem divider>
+              <ListItemIcon>
+                <ImageIcon />
+              </ListItemIcon>
+              <TextField
+                label="Map thumbnail source"
+                helperText="Image URL, {map} is replaced with the map name"
+                placeholder="https://example.com/thumbnails/{map}.jpg"
+                value={settings.map_thumbnail_url}
+                onChange={(event) => {
+                  this.textSettingChanged(
+                    "map_thumbnail_url",
+                    event.target.value
+                  );
+                }}
+                spellCheck={false}
+                fullWidth
+              />
+            </ListItem>
+            <ListItem divider>
+              <ListItemIcon>
+                <ShareIcon />
+              </ListItemIcon>
+              <div style={{ width: "100%" }}>
+                <TextField
+                  label="GitHub token for sharing events"
+                  helperText="Events are shared as secret gists if set"
+                  type="password"
+                  value={settings.gist_token}
+                  onChange={(event) => {
+                    this.textSettingChanged("gist_token", event.target.value);
+                  }}
+                  spellCheck={false}
+                  fullWidth
+                />
+                <TextField
+                  label="Paste service"
+                  helperText="URL the events are posted to without a GitHub token"
+                  placeholder={DEFAULT_PASTE_URL}
+                  value={settings.paste_url}
+                  onChange={(event) => {
+                    this.textSettingChanged("paste_url", event.target.value);
+                  }}
+                  disabled={settings.gist_token !== ""}
+                  spellCheck={false}
+                  fullWidth
+                  margin="normal"
+                />
+              </div>
+            </ListItem>
+            <ListItem>
+              <ListItemIcon>
+                <SettingsRemoteIcon />
+              </ListItemIcon>
+              <div style={{ width: "100%" }}>
+                <TextField
+                  label="RCON connection timeout (seconds)"
+                  type="number"
+                  value={settings.rcon_connect_timeout / 1000}
+                  onChange={(event) => {
+                    this.numberSettingChanged(
+                      "rcon_connect_timeout",
+                      Number(event.target.value) * 1000,
+                      1000
+                    );
+                  }}
+                  inputProps={{ min: 1 }}
+                  fullWidth
+                />
+                <TextField
+                  label="RCON command timeout (seconds)"
+                  helperText="Long commands like cvarlist may need more time"
+                  type="number"
+                  value={settings.rcon_command_timeout / 1000}
+                  onChange={(event) => {
+                    this.numberSettingChanged(
+                      "rcon_command_timeout",
+                      Number(event.target.value) * 1000,
+                      1000
+                    );
+                  }}
+                  inputProps={{ min: 1 }}
+                  fullWidth
+                  margin="normal"
+                />
+                <TextField
+                  label="RCON connection retries"
+                  helperText="Additional attempts to connect to a remote server"
+                  type="number"
+                  value={settings.rcon_connect_retries}
+                  onChange={(event) => {
+                    this.numberSettingChanged(
+                      "rcon_connect_retries",
+                      Number(event.target.value),
+                      0
+                    );
+                  }}
+                  inputProps={{ min: 0 }}
+                  fullWidth
+                  margin="normal"
+                />
+              </div>
+            </ListItem>
           </List>
           <Menu
             anchorEl={themePickerAnchor}
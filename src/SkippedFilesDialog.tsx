@@ -0,0 +1,71 @@
+import React from "react";
+import path from "path";
+
+import Button from "@material-ui/core/Button";
+import DialogContentText from "@material-ui/core/DialogContentText";
+import List from "@material-ui/core/List";
+import ListItem from "@material-ui/core/ListItem";
+import ListItemText from "@material-ui/core/ListItemText";
+
+import SmallDialog from "./SmallDialog";
+import { SkippedFile } from "./Demos";
+
+type SkippedFilesDialogProps = {
+  files: SkippedFile[];
+  ref: React.RefObject<SkippedFilesDialog>;
+};
+
+type SkippedFilesDialogState = {
+  open: boolean;
+};
+
+export default class SkippedFilesDialog extends React.Component<
+  SkippedFilesDialogProps,
+  SkippedFilesDialogState
+> {
+  constructor(props: SkippedFilesDialogProps) {
+    super(props);
+    this.state = { open: false };
+  }
+
+  open = () => {
+    this.setState({ open: true });
+  };
+
+  close = () => {
+    this.setState({ open: false });
+  };
+
+  render() {
+    const { files } = this.props;
+    const { open } = this.state;
+    return (
+      <SmallDialog
+        title="Skipped files"
+        open={open}
+        onClose={this.close}
+        maxWidth="sm"
+        actions={
+          <Button variant="contained" onClick={this.close}>
+            Close
+          </Button>
+        }
+      >
+        <DialogContentText>
+          These files in the demo folder could not be read as demos and
+          aren&apos;t listed.
+        </DialogContentText>
+        <List dense>
+          {files.map((file) => (
+            <ListItem key={file.filename}>
+              <ListItemText
+                primary={path.basename(file.filename)}
+                secondary={file.reason}
+              />
+            </ListItem>
+          ))}
+        </List>
+      </SmallDialog>
+    );
+  }
+}
@@ -0,0 +1,175 @@
+import fs from "fs";
+import path from "path";
+import { remote } from "electron";
+import log from "electron-log";
+
+import { getJson } from "./Http";
+import { getSteamUserId } from "./Tf2Config";
+import { getCredential } from "./Credentials";
+
+const API_URL = "https://api.steampowered.com";
+// Maximum number of SteamIDs per GetPlayerSummaries/GetPlayerBans request
+const MAX_IDS_PER_REQUEST = 100;
+// How long cached profiles are used before fetching them again
+const PROFILE_CACHE_DURATION = 24 * 60 * 60 * 1000;
+
+export interface PlayerProfile {
+  steamId64: string;
+  personaName: string;
+  avatarUrl: string;
+  profileUrl: string;
+  fetched: number;
+}
+
+function getCachePath() {
+  return path.join(remote.app.getPath("userData"), "steam_profiles.json");
+}
+
+function readCache(): Record<string, PlayerProfile> {
+  try {
+    return JSON.parse(fs.readFileSync(getCachePath()).toString());
+  } catch (e) {
+    return {};
+  }
+}
+
+function writeCache(cache: Record<string, PlayerProfile>) {
+  fs.writeFileSync(getCachePath(), JSON.stringify(cache));
+}
+
+interface FriendListResponse {
+  friendslist: {
+    friends: { steamid: string }[];
+  };
+}
+
+// Friends of the user, loaded once per session
+let friendIds: Set<string> | undefined;
+
+/**
+ * Returns the SteamID64s of the user's Steam friends, using the account
+ * last logged in to Steam. Returns an empty set without a Steam Web API
+ * key or if the friends list is private.
+ */
+export async function getFriendIds() {
+  const apiKey = await getCredential("steam_api_key");
+  const steamId = getSteamUserId();
+  if (apiKey === "" || steamId === undefined) {
+    return new Set<string>();
+  }
+  if (friendIds === undefined) {
+    log.debug("Fetching Steam friends list");
+    try {
+      const result = await getJson<FriendListResponse>(
+        `${API_URL}/ISteamUser/GetFriendList/v1/?key=${apiKey}&steamid=${steamId}`
+      );
+      friendIds = new Set(
+        result.friendslist.friends.map((friend) => friend.steamid)
+      );
+    } catch (e) {
+      // Private friends lists are answered with 401
+      log.warn(`Could not load the Steam friends list: ${e}`);
+      friendIds = new Set();
+    }
+  }
+  return friendIds;
+}
+
+interface PlayerSummariesResponse {
+  response: {
+    players: {
+      steamid: string;
+      personaname: string;
+      avatarfull: string;
+      profileurl: string;
+    }[];
+  };
+}
+
+/**
+ * Returns the current persona names and avatars of the given players,
+ * using the Steam Web API key set by the user. Profiles are cached on disk.
+ */
+export async function getPlayerProfiles(steamIds64: string[]) {
+  const apiKey = await getCredential("steam_api_key");
+  const cache = readCache();
+  const now = Date.now();
+  const missing = steamIds64.filter(
+    (id) =>
+      cache[id] === undefined ||
+      now - cache[id].fetched > PROFILE_CACHE_DURATION
+  );
+
+  if (missing.length !== 0 && apiKey !== "") {
+    log.debug(`Fetching ${missing.length} Steam profiles`);
+    for (let i = 0; i < missing.length; i += MAX_IDS_PER_REQUEST) {
+      const ids = missing.slice(i, i + MAX_IDS_PER_REQUEST).join(",");
+      // eslint-disable-next-line no-await-in-loop
+      const result = await getJson<PlayerSummariesResponse>(
+        `${API_URL}/ISteamUser/GetPlayerSummaries/v2/?key=${apiKey}&steamids=${ids}`
+      );
+      result.response.players.forEach((player) => {
+        cache[player.steamid] = {
+          steamId64: player.steamid,
+          personaName: player.personaname,
+          avatarUrl: player.avatarfull,
+          profileUrl: player.profileurl,
+          fetched: now,
+        };
+      });
+    }
+    writeCache(cache);
+  }
+
+  const profiles: Record<string, PlayerProfile> = {};
+  steamIds64.forEach((id) => {
+    if (cache[id] !== undefined) {
+      profiles[id] = cache[id];
+    }
+  });
+  return profiles;
+}
+
+export interface PlayerBans {
+  vacBans: number;
+  gameBans: number;
+  daysSinceLastBan: number;
+}
+
+interface PlayerBansResponse {
+  players: {
+    SteamId: string;
+    NumberOfVACBans: number;
+    NumberOfGameBans: number;
+    DaysSinceLastBan: number;
+  }[];
+}
+
+/**
+ * Returns the VAC and game bans of the given players that have any.
+ * Bans can be added at any time, so they aren't cached.
+ */
+export async function getPlayerBans(steamIds64: string[]) {
+  const apiKey = await getCredential("steam_api_key");
+  const bans: Record<string, PlayerBans> = {};
+  if (apiKey === "") {
+    return bans;
+  }
+  for (let i = 0; i < steamIds64.length; i += MAX_IDS_PER_REQUEST) {
+    const ids = steamIds64.slice(i, i + MAX_IDS_PER_REQUEST).join(",");
+    // eslint-disable-next-line no-await-in-loop
+    const result = await getJson<PlayerBansResponse>(
+      `${API_URL}/ISteamUser/GetPlayerBans/v1/?key=${apiKey}&steamids=${ids}`
+    );
+    result.players
+      .filter((player) => player.NumberOfVACBans + player.NumberOfGameBans > 0)
+      .forEach((player) => {
+        bans[player.SteamId] = {
+          vacBans: player.NumberOfVACBans,
+          gameBans: player.NumberOfGameBans,
+          daysSinceLastBan: player.DaysSinceLastBan,
+        };
+      });
+  }
+  return bans;
+}
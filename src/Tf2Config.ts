@@ -0,0 +1,255 @@
+import { execFileSync } from "child_process";
+import fs from "fs";
+import os from "os";
+import path from "path";
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import { getVdfValue, parseVdf } from "./Vdf";
+
+// The port TF2 listens on for RCON if no other port is configured
+const DEFAULT_RCON_PORT = 27015;
+
+export interface RconSettings {
+  password?: string;
+  port: number;
+  // Whether -usercon is part of the launch options.
+  // Without it, the game doesn't accept RCON connections.
+  usercon: boolean;
+}
+
+function getDefaultSteamDirs() {
+  switch (process.platform) {
+    case "win32":
+      return ["C:\\Program Files (x86)\\Steam", "C:\\Program Files\\Steam"];
+    case "darwin":
+      return [path.join(os.homedir(), "Library/Application Support/Steam")];
+    default:
+      return [
+        path.join(os.homedir(), ".local/share/Steam"),
+        path.join(os.homedir(), ".steam/steam"),
+      ];
+  }
+}
+
+// Steam stores its install location in the registry on Windows
+function getSteamDirFromRegistry() {
+  try {
+    const output = execFileSync("reg", [
+      "query",
+      "HKCU\\Software\\Valve\\Steam",
+      "/v",
+      "SteamPath",
+    ]).toString();
+    const match = /SteamPath\s+REG_SZ\s+(.+)/.exec(output);
+    return match === null ? undefined : path.normalize(match[1].trim());
+  } catch (e) {
+    log.debug(`Could not read the Steam path from the registry: ${e}`);
+    return undefined;
+  }
+}
+
+/**
+ * Finds the Steam install directory, which contains the user configs.
+ * Games may be installed in other library folders, see getSteamLibraryDirs.
+ */
+export function getSteamDir(): string | undefined {
+  const candidates = getDefaultSteamDirs();
+  if (process.platform === "win32") {
+    const registryDir = getSteamDirFromRegistry();
+    if (registryDir !== undefined) {
+      candidates.unshift(registryDir);
+    }
+  }
+  return candidates.find((dir) => fs.existsSync(path.join(dir, "steamapps")));
+}
+
+function readVdfFile(vdfPath: string) {
+  try {
+    return parseVdf(fs.readFileSync(vdfPath).toString());
+  } catch (e) {
+    if (e.code !== "ENOENT") {
+      log.warn(`Error reading ${vdfPath}: ${e}`);
+    }
+    return undefined;
+  }
+}
+
+/**
+ * Returns the SteamID64 of the account that last logged in to Steam,
+ * which is usually the one playing TF2.
+ */
+export function getSteamUserId(): string | undefined {
+  const steamDir = getSteamDir();
+  const vdf =
+    steamDir && readVdfFile(path.join(steamDir, "config/loginusers.vdf"));
+  const users = vdf && getVdfValue(vdf, "users");
+  if (users === undefined || typeof users === "string") {
+    return undefined;
+  }
+  const ids = Object.keys(users);
+  return (
+    ids.find((id) => getVdfValue(users, id, "MostRecent") === "1") ?? ids[0]
+  );
+}
+
+// Returns the Steam directory and all library folders added in Steam
+function getSteamLibraryDirs(steamDir: string) {
+  const libraryDirs = [steamDir];
+  const vdf = readVdfFile(path.join(steamDir, "steamapps/libraryfolders.vdf"));
+  const folders = vdf && getVdfValue(vdf, "libraryfolders");
+  if (folders !== undefined && typeof folders !== "string") {
+    Object.entries(folders).forEach(([key, value]) => {
+      // Older versions list the paths directly, newer ones in a "path" key.
+      // Other keys like "ContentStatsID" aren't numbers.
+      const libraryDir =
+        typeof value === "string" ? value : getVdfValue(value, "path");
+      if (/^\d+$/.test(key) && typeof libraryDir === "string") {
+        libraryDirs.push(path.normalize(libraryDir));
+      }
+    });
+  }
+  return libraryDirs;
+}
+
+/**
+ * Finds the game's "tf" directory, either from the configured demo path
+ * (which is usually tf/ or tf/demos/) or from the Steam library folders.
+ */
+export function getTf2Dir(): string | undefined {
+  const demoDir: string | undefined = cfg.get("demo_path");
+  if (demoDir !== undefined) {
+    const candidates = [demoDir, path.dirname(demoDir)];
+    const tfDir = candidates.find(
+      (dir) =>
+        path.basename(dir) === "tf" && fs.existsSync(path.join(dir, "cfg"))
+    );
+    if (tfDir !== undefined) {
+      return tfDir;
+    }
+  }
+  const steamDir = getSteamDir();
+  if (steamDir === undefined) {
+    return undefined;
+  }
+  return getSteamLibraryDirs(steamDir)
+    .map((libraryDir) =>
+      path.join(libraryDir, "steamapps/common/Team Fortress 2/tf")
+    )
+    .find((dir) => fs.existsSync(dir));
+}
+
+function splitArgs(args: string) {
+  const matches = args.match(/"[^"]*"|\S+/g) || [];
+  return matches.map((arg) => arg.replace(/^"(.*)"$/, "$1"));
+}
+
+// Returns the RCON settings set by the arguments or commands
+function parseArgs(args: string[]) {
+  const settings: Partial<RconSettings> = {};
+  for (let i = 0; i < args.length; i += 1) {
+    const arg = args[i].replace(/^\+/, "");
+    const value = args[i + 1];
+    if (arg === "-usercon") {
+      settings.usercon = true;
+    } else if (arg === "rcon_password" && value !== undefined) {
+      settings.password = value;
+    } else if (
+      (arg === "-port" || arg === "hostport") &&
+      value !== undefined &&
+      !Number.isNaN(parseInt(value, 10))
+    ) {
+      settings.port = parseInt(value, 10);
+    }
+  }
+  return settings;
+}
+
+function readConfigFile(cfgPath: string) {
+  let settings: Partial<RconSettings> = {};
+  let content;
+  try {
+    content = fs.readFileSync(cfgPath).toString();
+  } catch (e) {
+    if (e.code === "ENOENT") {
+      return settings;
+    }
+    throw e;
+  }
+  log.debug(`Reading RCON settings from ${cfgPath}`);
+  content.split("\n").forEach((line) => {
+    line
+      .split("//", 1)[0]
+      .split(";")
+      .forEach((command) => {
+        settings = { ...settings, ...parseArgs(splitArgs(command)) };
+      });
+  });
+  return settings;
+}
+
+function readLaunchOptions(steamDir: string) {
+  let settings: Partial<RconSettings> = {};
+  const userdataDir = path.join(steamDir, "userdata");
+  let users: string[];
+  try {
+    users = fs.readdirSync(userdataDir);
+  } catch (e) {
+    return settings;
+  }
+  users.forEach((user) => {
+    const vdf = readVdfFile(
+      path.join(userdataDir, user, "config/localconfig.vdf")
+    );
+    const launchOptions =
+      vdf &&
+      getVdfValue(
+        vdf,
+        "UserLocalConfigStore",
+        "Software",
+        "Valve",
+        "Steam",
+        "apps",
+        "440",
+        "LaunchOptions"
+      );
+    if (typeof launchOptions === "string") {
+      log.debug(`Reading RCON settings from launch options of user ${user}`);
+      settings = { ...settings, ...parseArgs(splitArgs(launchOptions)) };
+    }
+  });
+  return settings;
+}
+
+/**
+ * Reads the RCON password and port from the user's TF2 launch options
+ * and autoexec files, so they don't have to be entered manually.
+ */
+export function discoverRconSettings(): RconSettings {
+  let settings: RconSettings = { port: DEFAULT_RCON_PORT, usercon: false };
+  const steamDir = getSteamDir();
+  if (steamDir === undefined) {
+    log.info("Could not find the Steam directory");
+  } else {
+    settings = { ...settings, ...readLaunchOptions(steamDir) };
+  }
+
+  const tfDir = getTf2Dir();
+  if (tfDir === undefined) {
+    log.info("Could not find the TF2 directory");
+    return settings;
+  }
+
+  const cfgFiles = [path.join(tfDir, "cfg/autoexec.cfg")];
+  try {
+    fs.readdirSync(path.join(tfDir, "custom")).forEach((dir) => {
+      cfgFiles.push(path.join(tfDir, "custom", dir, "cfg/autoexec.cfg"));
+    });
+  } catch (e) {
+    // There is no custom folder, ignore
+  }
+  cfgFiles.forEach((cfgPath) => {
+    settings = { ...settings, ...readConfigFile(cfgPath) };
+  });
+  return settings;
+}
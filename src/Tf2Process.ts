@@ -0,0 +1,39 @@
+import { execFile } from "child_process";
+import path from "path";
+import { promisify } from "util";
+import log from "electron-log";
+
+const execFileAsync = promisify(execFile);
+
+// Executable names of the game on all platforms, old and new (64-bit)
+const TF2_PROCESS_NAMES = [
+  "hl2.exe",
+  "tf.exe",
+  "tf_win64.exe",
+  "hl2_linux",
+  "tf_linux64",
+  "hl2_osx",
+];
+
+async function getProcessNames() {
+  if (process.platform === "win32") {
+    const { stdout } = await execFileAsync("tasklist", ["/fo", "csv", "/nh"]);
+    // Lines look like "hl2.exe","1234","Console","1","100,000 K"
+    return stdout.split("\n").map((line) => line.split('"')[1] ?? "");
+  }
+  const { stdout } = await execFileAsync("ps", ["-A", "-o", "comm="]);
+  return stdout.split("\n").map((line) => path.basename(line.trim()));
+}
+
+export default async function isTf2Running() {
+  let processNames;
+  try {
+    processNames = await getProcessNames();
+  } catch (e) {
+    log.warn(`Could not list running processes: ${e}`);
+    return false;
+  }
+  return processNames.some((name) =>
+    TF2_PROCESS_NAMES.includes(name.toLowerCase())
+  );
+}
@@ -0,0 +1,176 @@
+import crypto from "crypto";
+import { EventEmitter } from "events";
+import path from "path";
+import cfg from "electron-cfg";
+import log from "electron-log";
+
+import { Demo } from "./Demos";
+import { uploadToDemosTf } from "./DemosTf";
+import { getCredential } from "./Credentials";
+import jobManager, { JobCancelledError, JobContext } from "./Jobs";
+
+// Failed uploads are retried after 1, 2, 4, ... minutes
+const BASE_RETRY_DELAY = 60000;
+const MAX_ATTEMPTS = 5;
+
+export type UploadTarget = "demostf";
+
+export type UploadStatus = "pending" | "uploading" | "done" | "failed";
+
+export interface UploadQueueEntry {
+  id: string;
+  filename: string;
+  target: UploadTarget;
+  status: UploadStatus;
+  attempts: number;
+  // Timestamp before which the upload isn't retried
+  nextAttempt: number;
+  url?: string;
+  error?: string;
+}
+
+// Thrown for uploads that can't succeed until the user changes a setting,
+// so they aren't retried
+export class UploadSettingsError extends Error {
+  constructor(message: string) {
+    super(message);
+    this.name = "UploadSettingsError";
+  }
+}
+
+type Uploader = (
+  demo: Demo,
+  job: JobContext,
+  maxBytesPerSecond?: number
+) => Promise<string>;
+
+const uploaders: Record<UploadTarget, Uploader> = {
+  demostf: async (demo, job, maxBytesPerSecond) => {
+    const apiKey = await getCredential("demostf_api_key");
+    if (apiKey === "") {
+      throw new UploadSettingsError("No demos.tf API key is set");
+    }
+    return uploadToDemosTf(demo, apiKey, job, maxBytesPerSecond);
+  },
+};
+
+export function getUploadQueueEntries(): UploadQueueEntry[] {
+  return cfg.get("upload_queue", []);
+}
+
+/**
+ * Uploads demos one after another, retrying failed uploads with an
+ * increasing delay. The queue is stored in the settings, so pending
+ * uploads continue after a restart. Emits a "changed" event with the
+ * entries whenever the queue changes.
+ */
+class UploadQueue extends EventEmitter {
+  running = false;
+
+  timer?: NodeJS.Timeout;
+
+  private setEntries(entries: UploadQueueEntry[]) {
+    cfg.set("upload_queue", entries);
+    this.emit("changed", entries);
+  }
+
+  private updateEntry(id: string, update: Partial<UploadQueueEntry>) {
+    this.setEntries(
+      getUploadQueueEntries().map((entry) =>
+        entry.id === id ? { ...entry, ...update } : entry
+      )
+    );
+  }
+
+  add(demo: Demo, target: UploadTarget) {
+    log.info(`Queueing upload of ${demo.filename} to ${target}`);
+    this.setEntries([
+      ...getUploadQueueEntries(),
+      {
+        id: crypto.randomBytes(8).toString("hex"),
+        filename: demo.filename,
+        target,
+        status: "pending",
+        attempts: 0,
+        nextAttempt: 0,
+      },
+    ]);
+    this.processNext();
+  }
+
+  retry(id: string) {
+    this.updateEntry(id, { status: "pending", attempts: 0, nextAttempt: 0 });
+    this.processNext();
+  }
+
+  // Removes the entry, an upload that's already running isn't cancelled
+  remove(id: string) {
+    this.setEntries(getUploadQueueEntries().filter((entry) => entry.id !== id));
+  }
+
+  start() {
+    // Uploads that were running when DemoMan was closed start over
+    this.setEntries(
+      getUploadQueueEntries().map((entry) =>
+        entry.status === "uploading"
+          ? { ...entry, status: "pending" as UploadStatus }
+          : entry
+      )
+    );
+    this.processNext();
+  }
+
+  private processNext = async () => {
+    if (this.running) {
+      return;
+    }
+    if (this.timer !== undefined) {
+      clearTimeout(this.timer);
+      this.timer = undefined;
+    }
+    const pending = getUploadQueueEntries().filter(
+      (entry) => entry.status === "pending"
+    );
+    const entry = pending.find((e) => e.nextAttempt <= Date.now());
+    if (entry === undefined) {
+      if (pending.length !== 0) {
+        const next = Math.min(...pending.map((e) => e.nextAttempt));
+        this.timer = setTimeout(this.processNext, next - Date.now());
+      }
+      return;
+    }
+
+    this.running = true;
+    this.updateEntry(entry.id, { status: "uploading" });
+    const limit: number = cfg.get("upload_bandwidth_limit", 0);
+    try {
+      const demo = await Demo.create(entry.filename);
+      const url = await jobManager.run(
+        "upload",
+        `Uploading ${path.basename(entry.filename)}`,
+        (job) =>
+          uploaders[entry.target](demo, job, limit > 0 ? limit : undefined)
+      );
+      this.updateEntry(entry.id, { status: "done", url, error: undefined });
+    } catch (e) {
+      const attempts = entry.attempts + 1;
+      log.warn(`Upload of ${entry.filename} failed (${attempts}): ${e}`);
+      // Cancelled uploads can be started again with retry
+      const retry =
+        attempts < MAX_ATTEMPTS &&
+        !(e instanceof UploadSettingsError) &&
+        !(e instanceof JobCancelledError);
+      this.updateEntry(entry.id, {
+        status: retry ? "pending" : "failed",
+        attempts,
+        nextAttempt: Date.now() + BASE_RETRY_DELAY * 2 ** (attempts - 1),
+        error: e.message,
+      });
+    }
+    this.running = false;
+    this.processNext();
+  };
+}
+
+const uploadQueue = new UploadQueue();
+export default uploadQueue;
@@ -0,0 +1,151 @@
+import React from "react";
+import path from "path";
+import { shell } from "electron";
+
+import Button from "@material-ui/core/Button";
+import DialogContentText from "@material-ui/core/DialogContentText";
+import IconButton from "@material-ui/core/IconButton";
+import Link from "@material-ui/core/Link";
+import List from "@material-ui/core/List";
+import ListItem from "@material-ui/core/ListItem";
+import ListItemSecondaryAction from "@material-ui/core/ListItemSecondaryAction";
+import ListItemText from "@material-ui/core/ListItemText";
+import Tooltip from "@material-ui/core/Tooltip";
+import DeleteIcon from "@material-ui/icons/Delete";
+import ReplayIcon from "@material-ui/icons/Replay";
+
+import SmallDialog from "./SmallDialog";
+import uploadQueue, {
+  getUploadQueueEntries,
+  UploadQueueEntry,
+} from "./UploadQueue";
+
+const TARGET_NAMES = {
+  demostf: "demos.tf",
+};
+
+type UploadQueueDialogProps = {
+  ref: React.RefObject<UploadQueueDialog>;
+};
+
+type UploadQueueDialogState = {
+  open: boolean;
+  entries: UploadQueueEntry[];
+};
+
+function formatStatus(entry: UploadQueueEntry) {
+  const target = TARGET_NAMES[entry.target];
+  switch (entry.status) {
+    case "uploading":
+      return `Uploading to ${target}`;
+    case "done":
+      return (
+        <Link
+          href={entry.url}
+          onClick={(event: React.MouseEvent) => {
+            event.preventDefault();
+            if (entry.url !== undefined) {
+              shell.openExternal(entry.url);
+            }
+          }}
+        >
+          {entry.url}
+        </Link>
+      );
+    case "failed":
+      return `Failed: ${entry.error}`;
+    default:
+      if (entry.attempts === 0) {
+        return `Waiting to upload to ${target}`;
+      }
+      return (
+        `Attempt ${entry.attempts} failed (${entry.error}), retrying at ` +
+        `${new Date(entry.nextAttempt).toLocaleTimeString()}`
+      );
+  }
+}
+
+/**
+ * Shows the uploads in the queue, with buttons to retry failed uploads
+ * and to remove entries.
+ */
+export default class UploadQueueDialog extends React.Component<
+  UploadQueueDialogProps,
+  UploadQueueDialogState
+> {
+  constructor(props: UploadQueueDialogProps) {
+    super(props);
+    this.state = { open: false, entries: getUploadQueueEntries() };
+  }
+
+  componentDidMount() {
+    uploadQueue.on("changed", this.onQueueChanged);
+  }
+
+  componentWillUnmount() {
+    uploadQueue.off("changed", this.onQueueChanged);
+  }
+
+  onQueueChanged = (entries: UploadQueueEntry[]) => {
+    this.setState({ entries });
+  };
+
+  open = () => {
+    this.setState({ open: true, entries: getUploadQueueEntries() });
+  };
+
+  close = () => {
+    this.setState({ open: false });
+  };
+
+  render() {
+    const { open, entries } = this.state;
+    return (
+      <SmallDialog
+        title="Uploads"
+        open={open}
+        onClose={this.close}
+        maxWidth="sm"
+        actions={
+          <Button variant="contained" onClick={this.close}>
+            Close
+          </Button>
+        }
+      >
+        {entries.length === 0 ? (
+          <DialogContentText>No uploads are queued.</DialogContentText>
+        ) : (
+          <List dense>
+            {entries.map((entry) => (
+              // Leaves room for both buttons
+              <ListItem key={entry.id} style={{ paddingRight: "104px" }}>
+                <ListItemText
+                  primary={path.basename(entry.filename)}
+                  secondary={formatStatus(entry)}
+                />
+                <ListItemSecondaryAction>
+                  {(entry.status === "failed" ||
+                    (entry.status === "pending" && entry.attempts > 0)) && (
+                    <Tooltip title="Retry now">
+                      <IconButton onClick={() => uploadQueue.retry(entry.id)}>
+                        <ReplayIcon />
+                      </IconButton>
+                    </Tooltip>
+                  )}
+                  <Tooltip title="Remove">
+                    <IconButton
+                      edge="end"
+                      onClick={() => uploadQueue.remove(entry.id)}
+                    >
+                      <DeleteIcon />
+                    </IconButton>
+                  </Tooltip>
+                </ListItemSecondaryAction>
+              </ListItem>
+            ))}
+          </List>
+        )}
+      </SmallDialog>
+    );
+  }
+}
@@ -0,0 +1,108 @@
+// Steam's KeyValues text format, used by its config files
+export interface VdfObject {
+  [key: string]: string | VdfObject;
+}
+
+const ESCAPES: Record<string, string> = { n: "\n", t: "\t" };
+
+const UNQUOTED_TOKEN = /[^\s{}"]+/y;
+
+function tokenize(text: string) {
+  const tokens: { value: string; quoted: boolean }[] = [];
+  let i = 0;
+  while (i < text.length) {
+    const char = text[i];
+    if (/\s/.test(char)) {
+      i += 1;
+    } else if (text.startsWith("//", i)) {
+      const end = text.indexOf("\n", i);
+      i = end === -1 ? text.length : end;
+    } else if (char === "{" || char === "}") {
+      tokens.push({ value: char, quoted: false });
+      i += 1;
+    } else if (char === '"') {
+      let value = "";
+      i += 1;
+      while (i < text.length && text[i] !== '"') {
+        if (text[i] === "\\" && i + 1 < text.length) {
+          value += ESCAPES[text[i + 1]] ?? text[i + 1];
+          i += 2;
+        } else {
+          value += text[i];
+          i += 1;
+        }
+      }
+      tokens.push({ value, quoted: true });
+      i += 1;
+    } else {
+      UNQUOTED_TOKEN.lastIndex = i;
+      const [value] = UNQUOTED_TOKEN.exec(text) as RegExpExecArray;
+      // Conditions like [$WIN32] apply to the preceding key or value,
+      // they are ignored
+      if (!/^\[.*\]$/.test(value)) {
+        tokens.push({ value, quoted: false });
+      }
+      i += value.length;
+    }
+  }
+  return tokens;
+}
+
+/**
+ * Parses a VDF file like Steam's localconfig.vdf. Throws if the braces
+ * don't match up. Later duplicate keys replace earlier ones.
+ */
+export function parseVdf(text: string): VdfObject {
+  const tokens = tokenize(text);
+  const root: VdfObject = {};
+  const stack = [root];
+  for (let i = 0; i < tokens.length; i += 1) {
+    const token = tokens[i];
+    const current = stack[stack.length - 1];
+    if (token.value === "}" && !token.quoted) {
+      if (stack.length === 1) {
+        throw new Error("Unexpected } in VDF file");
+      }
+      stack.pop();
+    } else {
+      const next = tokens[i + 1];
+      if (next === undefined) {
+        throw new Error(`Missing value for key ${token.value} in VDF file`);
+      }
+      i += 1;
+      if (next.value === "{" && !next.quoted) {
+        const child: VdfObject = {};
+        current[token.value] = child;
+        stack.push(child);
+      } else {
+        current[token.value] = next.value;
+      }
+    }
+  }
+  if (stack.length !== 1) {
+    throw new Error("Missing } in VDF file");
+  }
+  return root;
+}
+
+/**
+ * Returns the value at the given path of keys. Keys are compared
+ * ignoring case, since Steam isn't consistent about it ("apps"/"Apps").
+ */
+export function getVdfValue(
+  vdf: VdfObject,
+  ...keys: string[]
+): string | VdfObject | undefined {
+  let current: string | VdfObject | undefined = vdf;
+  for (let i = 0; i < keys.length; i += 1) {
+    if (current === undefined || typeof current === "string") {
+      return undefined;
+    }
+    const object: VdfObject = current;
+    const key = Object.keys(object).find(
+      (k) => k.toLowerCase() === keys[i].toLowerCase()
+    );
+    current = key === undefined ? undefined : object[key];
+  }
+  return current;
+}
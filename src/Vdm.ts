@@ -0,0 +1,28 @@
+import { Demo } from "./Demos";
+import { normalizeFilePath } from "./util";
+
+// A demo action, e.g. { factory: "PlayCommands", starttick: "100", ... }
+export type VdmAction = Record<string, string>;
+
+// TF2 runs the actions in the VDM file next to a demo when playing it
+export function getVdmPath(demo: Demo) {
+  return normalizeFilePath(demo.filename).replace(/\.dem$/i, ".vdm");
+}
+
+/**
+ * Formats the actions as a VDM file. Comment lines are put at the top,
+ * they must not contain line breaks.
+ */
+export function formatVdm(actions: VdmAction[], comments: string[] = []) {
+  const lines = [...comments.map((comment) => `// ${comment}`)];
+  lines.push("demoactions", "{");
+  actions.forEach((action, index) => {
+    lines.push(`\t"${index + 1}"`, "\t{");
+    Object.entries(action).forEach(([key, value]) => {
+      lines.push(`\t\t${key} "${value}"`);
+    });
+    lines.push("\t}");
+  });
+  lines.push("}", "");
+  return lines.join("\n");
+}
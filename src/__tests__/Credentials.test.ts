@@ -0,0 +1,125 @@
+import cfg from "electron-cfg";
+import keytar from "keytar";
+
+import {
+  getCredential,
+  migrateCredentials,
+  setCredential,
+} from "../Credentials";
+
+jest.mock("electron-log");
+jest.mock("keytar", () => ({
+  getPassword: jest.fn(),
+  setPassword: jest.fn(),
+  deletePassword: jest.fn(),
+}));
+jest.mock("electron-cfg", () => {
+  let settings: Record<string, unknown> = {};
+  return {
+    get: (key: string, defaultValue?: unknown) =>
+      key in settings ? settings[key] : defaultValue,
+    set: (key: string, value: unknown) => {
+      settings[key] = value;
+    },
+    has: (key: string) => key in settings,
+    delete: (key: string) => {
+      delete settings[key];
+    },
+    reset: (values: Record<string, unknown>) => {
+      settings = values;
+    },
+  };
+});
+
+const keyring = keytar as jest.Mocked<typeof keytar>;
+
+function resetSettings(values: Record<string, unknown> = {}) {
+  (cfg as unknown as { reset: (v: Record<string, unknown>) => void }).reset(
+    values
+  );
+}
+
+beforeEach(() => {
+  jest.clearAllMocks();
+  resetSettings();
+  keyring.getPassword.mockResolvedValue(null);
+  keyring.setPassword.mockResolvedValue();
+  keyring.deletePassword.mockResolvedValue(true);
+});
+
+// Values are cached per session, so every test uses its own names
+
+describe("getCredential", () => {
+  it("reads from the keyring", async () => {
+    keyring.getPassword.mockResolvedValue("secret");
+    await expect(getCredential("read")).resolves.toBe("secret");
+    expect(keyring.getPassword).toHaveBeenCalledWith("DemoMan", "read");
+  });
+
+  it("falls back to the settings without a keyring", async () => {
+    keyring.getPassword.mockRejectedValue(new Error("no keyring"));
+    resetSettings({ credentials: { fallback: "secret" } });
+    await expect(getCredential("fallback")).resolves.toBe("secret");
+  });
+
+  it("returns an empty string for unset credentials", async () => {
+    await expect(getCredential("unset")).resolves.toBe("");
+  });
+});
+
+describe("setCredential", () => {
+  it("stores in the keyring and removes the fallback", async () => {
+    resetSettings({ credentials: { stored: "old" } });
+    await setCredential("stored", "new");
+    expect(keyring.setPassword).toHaveBeenCalledWith(
+      "DemoMan",
+      "stored",
+      "new"
+    );
+    expect(cfg.get("credentials")).toEqual({});
+    await expect(getCredential("stored")).resolves.toBe("new");
+  });
+
+  it("uses the settings if the keyring fails", async () => {
+    keyring.setPassword.mockRejectedValue(new Error("no keyring"));
+    await setCredential("unstored", "new");
+    expect(cfg.get("credentials")).toEqual({ unstored: "new" });
+  });
+
+  it("clears empty values", async () => {
+    await setCredential("cleared", "");
+    expect(keyring.deletePassword).toHaveBeenCalledWith("DemoMan", "cleared");
+    await expect(getCredential("cleared")).resolves.toBe("");
+  });
+});
+
+describe("migrateCredentials", () => {
+  it("moves plain text secrets into the store", async () => {
+    resetSettings({
+      steam_api_key: "steam",
+      gist_token: "",
+      rcon_servers: [
+        { name: "a", host: "a.example.com", port: 27015, password: "pw" },
+        { name: "b", host: "b.example.com", port: 27015 },
+      ],
+    });
+    await migrateCredentials();
+    expect(keyring.setPassword).toHaveBeenCalledWith(
+      "DemoMan",
+      "steam_api_key",
+      "steam"
+    );
+    expect(keyring.setPassword).toHaveBeenCalledWith(
+      "DemoMan",
+      "rcon_server:a",
+      "pw"
+    );
+    expect(keyring.setPassword).toHaveBeenCalledTimes(2);
+    expect(cfg.has("steam_api_key")).toBe(false);
+    expect(cfg.has("gist_token")).toBe(false);
+    expect(cfg.get("rcon_servers")).toEqual([
+      { name: "a", host: "a.example.com", port: 27015 },
+      { name: "b", host: "b.example.com", port: 27015 },
+    ]);
+  });
+});
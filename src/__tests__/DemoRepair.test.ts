@@ -0,0 +1,148 @@
+import fs from "fs";
+import os from "os";
+import path from "path";
+
+import { Demo, HEADER_SIZE } from "../Demos";
+import repairDemo, { DemoCommand, getFrameSize } from "../DemoRepair";
+
+jest.mock("electron", () => ({ remote: {} }));
+jest.mock("electron-cfg", () => ({ get: jest.fn(), set: jest.fn() }));
+jest.mock("electron-log");
+
+// Size of the frames written by the helpers below
+const SIGNON_FRAME_SIZE = 5 + 76 + 8 + 4 + 4;
+const PACKET_FRAME_SIZE = 5 + 76 + 8 + 4 + 2;
+const CONSOLE_CMD_FRAME_SIZE = 5 + 4 + 3;
+
+function frameHeader(cmd: DemoCommand, tick: number) {
+  const buf = Buffer.alloc(5);
+  buf.writeUInt8(cmd, 0);
+  buf.writeInt32LE(tick, 1);
+  return buf;
+}
+
+function frameData(data: Buffer) {
+  const length = Buffer.alloc(4);
+  length.writeInt32LE(data.length, 0);
+  return Buffer.concat([length, data]);
+}
+
+function packetFrame(cmd: DemoCommand, tick: number, data: Buffer) {
+  return Buffer.concat([
+    frameHeader(cmd, tick),
+    // Command info and sequence numbers
+    Buffer.alloc(76 + 8),
+    frameData(data),
+  ]);
+}
+
+function demoHeader() {
+  const buf = Buffer.alloc(HEADER_SIZE);
+  buf.write("HL2DEMO", 0);
+  buf.writeInt32LE(3, 8);
+  buf.writeInt32LE(24, 12);
+  buf.write("tf", 16 + 260 * 3);
+  // Playback time, ticks, frames and signon length are left at 0,
+  // like in a demo of a game that crashed while recording.
+  return buf;
+}
+
+// A demo that ends in the middle of a packet frame
+function truncatedDemo() {
+  return Buffer.concat([
+    demoHeader(),
+    packetFrame(DemoCommand.Signon, 0, Buffer.alloc(4)),
+    frameHeader(DemoCommand.SyncTick, 0),
+    packetFrame(DemoCommand.Packet, 66, Buffer.alloc(2)),
+    Buffer.concat([
+      frameHeader(DemoCommand.ConsoleCmd, 100),
+      frameData(Buffer.from("+x\0")),
+    ]),
+    packetFrame(DemoCommand.Packet, 132, Buffer.alloc(16)).slice(0, 25),
+  ]);
+}
+
+describe("getFrameSize", () => {
+  const buf = truncatedDemo();
+
+  it("returns the size of complete frames", () => {
+    let offset = HEADER_SIZE;
+    expect(getFrameSize(buf, offset)).toBe(SIGNON_FRAME_SIZE);
+    offset += SIGNON_FRAME_SIZE;
+    expect(getFrameSize(buf, offset)).toBe(5);
+    offset += 5;
+    expect(getFrameSize(buf, offset)).toBe(PACKET_FRAME_SIZE);
+    offset += PACKET_FRAME_SIZE;
+    expect(getFrameSize(buf, offset)).toBe(CONSOLE_CMD_FRAME_SIZE);
+  });
+
+  it("returns undefined for incomplete frames", () => {
+    const offset =
+      HEADER_SIZE +
+      SIGNON_FRAME_SIZE +
+      5 +
+      PACKET_FRAME_SIZE +
+      CONSOLE_CMD_FRAME_SIZE;
+    expect(getFrameSize(buf, offset)).toBeUndefined();
+    expect(getFrameSize(buf, buf.length - 2)).toBeUndefined();
+  });
+
+  it("returns undefined for invalid frames", () => {
+    expect(getFrameSize(Buffer.from([42, 0, 0, 0, 0]), 0)).toBeUndefined();
+    const negativeLength = Buffer.concat([
+      frameHeader(DemoCommand.ConsoleCmd, 0),
+      Buffer.from([0xff, 0xff, 0xff, 0xff]),
+    ]);
+    expect(getFrameSize(negativeLength, 0)).toBeUndefined();
+  });
+});
+
+describe("repairDemo", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await fs.promises.mkdtemp(path.join(os.tmpdir(), "demoman-"));
+  });
+
+  afterEach(async () => {
+    await fs.promises.rm(dir, { recursive: true, force: true });
+  });
+
+  it("cuts off the incomplete frame and fills in the header", async () => {
+    const input = path.join(dir, "crashed.dem");
+    const output = path.join(dir, "crashed_repaired.dem");
+    await fs.promises.writeFile(input, truncatedDemo());
+    const demo = await Demo.create(input);
+
+    const result = await repairDemo(demo, output);
+    expect(result).toEqual({ numTicks: 100, numFrames: 4, bytesDropped: 25 });
+
+    const buf = await fs.promises.readFile(output);
+    const framesEnd =
+      HEADER_SIZE +
+      SIGNON_FRAME_SIZE +
+      5 +
+      PACKET_FRAME_SIZE +
+      CONSOLE_CMD_FRAME_SIZE;
+    expect(buf.length).toBe(framesEnd + 5);
+    expect(buf.readFloatLE(HEADER_SIZE - 16)).toBeCloseTo(1.5);
+    expect(buf.readInt32LE(HEADER_SIZE - 12)).toBe(100);
+    expect(buf.readInt32LE(HEADER_SIZE - 8)).toBe(4);
+    expect(buf.readInt32LE(HEADER_SIZE - 4)).toBe(SIGNON_FRAME_SIZE);
+    // The frames themselves are copied unchanged
+    expect(buf.slice(HEADER_SIZE, framesEnd)).toEqual(
+      truncatedDemo().slice(HEADER_SIZE, framesEnd)
+    );
+    expect(buf.readUInt8(framesEnd)).toBe(DemoCommand.Stop);
+    expect(buf.readInt32LE(framesEnd + 1)).toBe(100);
+  });
+
+  it("refuses to overwrite the original demo", async () => {
+    const input = path.join(dir, "crashed.dem");
+    await fs.promises.writeFile(input, truncatedDemo());
+    const demo = await Demo.create(input);
+
+    await expect(repairDemo(demo, input)).rejects.toThrow();
+    expect(await fs.promises.readFile(input)).toEqual(truncatedDemo());
+  });
+});
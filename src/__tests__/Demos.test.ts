@@ -0,0 +1,135 @@
+import fs from "fs";
+
+import { Demo } from "../Demos";
+import { DemoHeader } from "../DemoHeader";
+import { getPlaydemoPath } from "../GameCommands";
+
+jest.mock("electron", () => ({ remote: {} }));
+jest.mock("electron-cfg", () => ({ get: jest.fn(), set: jest.fn() }));
+jest.mock("electron-log");
+jest.mock("fs");
+// The Windows path functions, so the tests behave the same on every OS
+jest.mock("path", () => jest.requireActual("path").win32);
+
+const platform = Object.getOwnPropertyDescriptor(process, "platform");
+
+// Demo.create reads the file, the tests only need the filename
+function createDemo(filename: string) {
+  const demo: Demo = Object.create(Demo.prototype);
+  demo.filename = filename;
+  demo.header = { gameDir: "tf" } as DemoHeader;
+  return demo;
+}
+
+const LONG_PATH = "\\\\?\\C:\\demos\\match.dem";
+const UNC_PATH = "\\\\nas\\share\\demos\\match.dem";
+const LONG_UNC_PATH = "\\\\?\\UNC\\nas\\share\\demos\\match.dem";
+
+beforeAll(() => {
+  Object.defineProperty(process, "platform", { value: "win32" });
+});
+
+afterAll(() => {
+  if (platform !== undefined) {
+    Object.defineProperty(process, "platform", platform);
+  }
+});
+
+beforeEach(() => {
+  jest.resetAllMocks();
+});
+
+describe("Demo.getJSONPath", () => {
+  it("strips the long path prefix", () => {
+    expect(Demo.getJSONPath(LONG_PATH)).toBe("C:\\demos\\match.json");
+    expect(Demo.getJSONPath(LONG_UNC_PATH)).toBe(
+      "\\\\nas\\share\\demos\\match.json"
+    );
+  });
+
+  it("keeps UNC paths", () => {
+    expect(Demo.getJSONPath(UNC_PATH)).toBe(
+      "\\\\nas\\share\\demos\\match.json"
+    );
+  });
+
+  it("only replaces the extension", () => {
+    expect(Demo.getJSONPath("C:\\a.dem.dem\\b.DEM")).toBe(
+      "C:\\a.dem.dem\\b.json"
+    );
+  });
+});
+
+describe("Demo.rename", () => {
+  it.each([
+    [LONG_PATH, "C:\\demos"],
+    [UNC_PATH, "\\\\nas\\share\\demos"],
+    [LONG_UNC_PATH, "\\\\nas\\share\\demos"],
+  ])("renames %s and its events file", (filename, dir) => {
+    const demo = createDemo(filename);
+    demo.rename("renamed");
+    expect(fs.renameSync).toHaveBeenCalledWith(
+      `${dir}\\match.dem`,
+      `${dir}\\renamed.dem`
+    );
+    expect(fs.renameSync).toHaveBeenCalledWith(
+      `${dir}\\match.json`,
+      `${dir}\\renamed.json`
+    );
+    expect(demo.filename).toBe(`${dir}\\renamed.dem`);
+  });
+
+  it("ignores a missing events file", () => {
+    (fs.renameSync as jest.Mock)
+      .mockImplementationOnce(() => undefined)
+      .mockImplementationOnce(() => {
+        throw Object.assign(new Error("not found"), { code: "ENOENT" });
+      });
+    const demo = createDemo(UNC_PATH);
+    demo.rename("renamed");
+    expect(demo.filename).toBe("\\\\nas\\share\\demos\\renamed.dem");
+  });
+
+  it("moves the demo back if the events file can't be renamed", () => {
+    (fs.renameSync as jest.Mock)
+      .mockImplementationOnce(() => undefined)
+      .mockImplementationOnce(() => {
+        throw Object.assign(new Error("busy"), { code: "EBUSY" });
+      });
+    const demo = createDemo(UNC_PATH);
+    expect(() => demo.rename("renamed")).toThrow("busy");
+    expect(fs.renameSync).toHaveBeenLastCalledWith(
+      "\\\\nas\\share\\demos\\renamed.dem",
+      UNC_PATH
+    );
+    expect(demo.filename).toBe(UNC_PATH);
+  });
+});
+
+describe("Demo.delete", () => {
+  it.each([
+    [LONG_PATH, "C:\\demos"],
+    [UNC_PATH, "\\\\nas\\share\\demos"],
+    [LONG_UNC_PATH, "\\\\nas\\share\\demos"],
+  ])("deletes %s and its events file", (filename, dir) => {
+    createDemo(filename).delete();
+    expect(fs.rmSync).toHaveBeenCalledWith(`${dir}\\match.dem`);
+    expect(fs.rmSync).toHaveBeenCalledWith(`${dir}\\match.json`);
+  });
+});
+
+describe("getPlaydemoPath", () => {
+  it("handles long paths on either side", () => {
+    const tfDir = "C:\\Steam\\steamapps\\common\\Team Fortress 2\\tf";
+    const demo = createDemo(`\\\\?\\${tfDir}\\demos\\match.dem`);
+    expect(getPlaydemoPath(demo, tfDir)).toBe("demos/match.dem");
+    expect(getPlaydemoPath(demo, `\\\\?\\${tfDir}`)).toBe("demos/match.dem");
+  });
+
+  it("rejects demos on another share", () => {
+    const demo = createDemo(UNC_PATH);
+    expect(() => getPlaydemoPath(demo, "\\\\nas\\other\\tf")).toThrow(
+      "outside of the tf directory"
+    );
+  });
+});
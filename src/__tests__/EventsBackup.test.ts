@@ -0,0 +1,95 @@
+import fs from "fs";
+import os from "os";
+import path from "path";
+
+import { importLegacyEvents, restoreEvents } from "../EventsBackup";
+
+jest.mock("electron", () => ({ remote: {} }));
+jest.mock("electron-cfg", () => ({ get: jest.fn(), set: jest.fn() }));
+jest.mock("electron-log");
+
+const event = { name: "Bookmark", value: "General", tick: 1000 };
+
+describe("restoreEvents", () => {
+  let dir: string;
+  let demoDir: string;
+
+  beforeEach(async () => {
+    dir = await fs.promises.mkdtemp(path.join(os.tmpdir(), "demoman-"));
+    demoDir = path.join(dir, "demos");
+    await fs.promises.mkdir(demoDir);
+  });
+
+  afterEach(async () => {
+    await fs.promises.rm(dir, { recursive: true, force: true });
+  });
+
+  async function writeJson(content: unknown) {
+    const filePath = path.join(dir, "events.json");
+    await fs.promises.writeFile(filePath, JSON.stringify(content));
+    return filePath;
+  }
+
+  function writeBackup(demos: Record<string, unknown>) {
+    return writeJson({ version: 1, demos });
+  }
+
+  it("restores events of existing demos", async () => {
+    await fs.promises.writeFile(path.join(demoDir, "demo.dem"), "");
+    const backupPath = await writeBackup({ demo: [event], missing: [event] });
+
+    expect(await restoreEvents(demoDir, backupPath)).toBe(1);
+    const json = await fs.promises.readFile(path.join(demoDir, "demo.json"));
+    expect(JSON.parse(json.toString()).events).toEqual([event]);
+  });
+
+  it("doesn't write outside of the demo folder", async () => {
+    // Makes sure the names would match an existing demo if they were used
+    await fs.promises.writeFile(path.join(dir, "outside.dem"), "");
+    const backupPath = await writeBackup({
+      "../outside": [event],
+      [`..${path.sep}outside`]: [event],
+      [path.join(dir, "outside")]: [event],
+    });
+
+    expect(await restoreEvents(demoDir, backupPath)).toBe(0);
+    expect(fs.existsSync(path.join(dir, "outside.json"))).toBe(false);
+  });
+});
+
+describe("importLegacyEvents", () => {
+  let dir: string;
+
+  beforeEach(async () => {
+    dir = await fs.promises.mkdtemp(path.join(os.tmpdir(), "demoman-"));
+  });
+
+  afterEach(async () => {
+    await fs.promises.rm(dir, { recursive: true, force: true });
+  });
+
+  it("imports lists and events objects of existing demos", async () => {
+    await fs.promises.writeFile(path.join(dir, "a.dem"), "");
+    await fs.promises.writeFile(path.join(dir, "b.dem"), "");
+    const filePath = path.join(dir, "legacy.json");
+    await fs.promises.writeFile(
+      filePath,
+      JSON.stringify({
+        "a.dem": [event, { name: "Broken" }],
+        b: { events: [event] },
+        "c.dem": [event],
+      })
+    );
+
+    expect(await importLegacyEvents(dir, filePath)).toBe(2);
+    const json = await fs.promises.readFile(path.join(dir, "a.json"));
+    expect(JSON.parse(json.toString()).events).toEqual([event]);
+    expect(fs.existsSync(path.join(dir, "b.json"))).toBe(true);
+  });
+
+  it("rejects files without events", async () => {
+    const filePath = path.join(dir, "legacy.json");
+    await fs.promises.writeFile(filePath, JSON.stringify({ a: "b" }));
+    await expect(importLegacyEvents(dir, filePath)).rejects.toThrow();
+  });
+});
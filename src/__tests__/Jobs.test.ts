@@ -0,0 +1,90 @@
+import jobManager, { JobCancelledError } from "../Jobs";
+
+jest.mock("electron-log");
+
+afterEach(() => {
+  jobManager.clearFinished();
+});
+
+describe("jobManager", () => {
+  it("records finished jobs", async () => {
+    await expect(
+      jobManager.run("export", "Exporting", async () => "result")
+    ).resolves.toBe("result");
+    await expect(
+      jobManager.run("scan", "Scanning", async () => {
+        throw new Error("unreadable");
+      })
+    ).rejects.toThrow("unreadable");
+    expect(jobManager.getJobs()).toMatchObject([
+      { type: "export", status: "done", progress: 1 },
+      { type: "scan", status: "failed", error: "unreadable" },
+    ]);
+  });
+
+  it("reports progress in whole percents", async () => {
+    const changed = jest.fn();
+    jobManager.on("changed", changed);
+    await jobManager.run("upload", "Uploading", async (job) => {
+      job.setProgress(0.501);
+      job.setProgress(0.505);
+      expect(jobManager.getJobs()[0].progress).toBe(0.5);
+      job.setProgress(0.51);
+      expect(jobManager.getJobs()[0].progress).toBe(0.51);
+    });
+    jobManager.off("changed", changed);
+    // Started, two progress updates and finished
+    expect(changed).toHaveBeenCalledTimes(4);
+  });
+
+  it("cancels jobs that support it", async () => {
+    let started: () => void = () => {};
+    const running = new Promise<void>((resolve) => {
+      started = resolve;
+    });
+    const result = jobManager.run("upload", "Uploading", (job) => {
+      const task = new Promise((resolve, reject) => {
+        job.onCancel(() => reject(new JobCancelledError()));
+      });
+      started();
+      return task;
+    });
+    await running;
+    const [{ id }] = jobManager.getJobs();
+    expect(jobManager.getJobs()[0].cancellable).toBe(true);
+    expect(jobManager.cancel(id)).toBe(true);
+    await expect(result).rejects.toThrow(JobCancelledError);
+    expect(jobManager.getJobs()[0]).toMatchObject({
+      status: "cancelled",
+      cancellable: false,
+    });
+    expect(jobManager.cancel(id)).toBe(false);
+  });
+
+  it("keeps running jobs when clearing finished ones", async () => {
+    let finish: () => void = () => {};
+    const result = jobManager.run(
+      "scan",
+      "Scanning",
+      () =>
+        new Promise<void>((resolve) => {
+          finish = resolve;
+        })
+    );
+    await jobManager.run("export", "Exporting", async () => {});
+    jobManager.clearFinished();
+    expect(jobManager.getJobs()).toMatchObject([{ status: "running" }]);
+    finish();
+    await result;
+  });
+
+  it("only keeps the latest finished jobs", async () => {
+    for (let i = 0; i < 25; i += 1) {
+      // eslint-disable-next-line no-await-in-loop
+      await jobManager.run("export", `Export ${i}`, async () => {});
+    }
+    const jobs = jobManager.getJobs();
+    expect(jobs).toHaveLength(20);
+    expect(jobs[0].description).toBe("Export 5");
+  });
+});
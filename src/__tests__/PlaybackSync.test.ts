@@ -0,0 +1,26 @@
+import { parseDemoInfo } from "../PlaybackSync";
+
+jest.mock("electron-cfg", () => ({ get: jest.fn(), set: jest.fn() }));
+jest.mock("electron-log");
+
+describe("parseDemoInfo", () => {
+  it("reads the playing demo and tick", () => {
+    const output = [
+      "Demo contents for demos/2021-05-01_20-00-00.dem:",
+      "Demo protocol 3, network protocol 24",
+      "Playback time 100.00 seconds, 6600 ticks",
+      "Current tick: 1234",
+      "",
+    ].join("\n");
+    expect(parseDemoInfo(output)).toEqual({
+      filename: "demos/2021-05-01_20-00-00.dem",
+      tick: 1234,
+    });
+  });
+
+  it("returns null while no demo is playing", () => {
+    expect(
+      parseDemoInfo("Error - Not currently playing back a demo.\n")
+    ).toBeNull();
+  });
+});
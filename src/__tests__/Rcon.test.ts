@@ -0,0 +1,154 @@
+import net from "net";
+
+import { RconConnection } from "../Rcon";
+
+jest.mock("electron-cfg", () => ({
+  get: jest.fn((_key: string, defaultValue: unknown) => defaultValue),
+  set: jest.fn(),
+}));
+jest.mock("electron-log");
+
+const PASSWORD = "secret";
+
+// Bodies longer than this are split across multiple packets, like the
+// game does with responses over 4096 bytes
+const MAX_BODY_LENGTH = 100;
+
+function buildPacket(id: number, type: number, body: string) {
+  const size = Buffer.byteLength(body) + 14;
+  const buffer = Buffer.alloc(size);
+  buffer.writeInt32LE(size - 4, 0);
+  buffer.writeInt32LE(id, 4);
+  buffer.writeInt32LE(type, 8);
+  buffer.write(body, 12, size - 2, "ascii");
+  return buffer;
+}
+
+/**
+ * Minimal RCON server answering commands with the given responses.
+ * Its output is written in small chunks, so packets are split across
+ * multiple data events.
+ */
+function startServer(responses: Record<string, string>) {
+  return new Promise<net.Server>((resolve) => {
+    const server = net.createServer((socket) => {
+      let buffer = Buffer.alloc(0);
+      socket.on("data", (data) => {
+        buffer = Buffer.concat([buffer, data]);
+        const output: Buffer[] = [];
+        while (
+          buffer.length >= 4 &&
+          buffer.length >= buffer.readInt32LE(0) + 4
+        ) {
+          const size = buffer.readInt32LE(0);
+          const id = buffer.readInt32LE(4);
+          const type = buffer.readInt32LE(8);
+          const body = buffer.toString("ascii", 12, size + 2);
+          buffer = buffer.slice(size + 4);
+          if (type === 3) {
+            output.push(buildPacket(id, 0, ""));
+            output.push(buildPacket(body === PASSWORD ? id : -1, 2, ""));
+          } else if (type === 2) {
+            const response = responses[body] ?? `Unknown command "${body}"\n`;
+            for (let i = 0; i < response.length; i += MAX_BODY_LENGTH) {
+              const part = response.slice(i, i + MAX_BODY_LENGTH);
+              output.push(buildPacket(id, 0, part));
+            }
+          } else {
+            // Empty response packets are mirrored, followed by this one
+            output.push(buildPacket(id, 0, ""));
+            output.push(buildPacket(id, 0, "\x00\x01\x00\x00"));
+          }
+        }
+        const bytes = Buffer.concat(output);
+        for (let i = 0; i < bytes.length; i += 37) {
+          socket.write(bytes.slice(i, i + 37));
+        }
+      });
+    });
+    server.listen(0, "127.0.0.1", () => resolve(server));
+  });
+}
+
+// Connections are closed after each test, so the server can shut down
+let connections: RconConnection[] = [];
+
+function connect(server: net.Server, password = PASSWORD) {
+  const { port } = server.address() as net.AddressInfo;
+  const connection = new RconConnection();
+  connections.push(connection);
+  return new Promise<RconConnection>((resolve, reject) => {
+    connection.connect(
+      port,
+      password,
+      () => resolve(connection),
+      reject,
+      "127.0.0.1"
+    );
+  });
+}
+
+describe("RconConnection", () => {
+  const longResponse = Array.from(
+    { length: 50 },
+    (_, i) => `line ${i}: ${"x".repeat(i)}\n`
+  ).join("");
+  let server: net.Server;
+
+  beforeEach(async () => {
+    server = await startServer({
+      echo: "echo\n",
+      cvarlist: longResponse,
+      status: "hostname: test\n",
+    });
+  });
+
+  afterEach((done) => {
+    connections.forEach((connection) => connection.disconnect());
+    connections = [];
+    server.close(() => done());
+  });
+
+  it("rejects a wrong password", async () => {
+    await expect(connect(server, "wrong")).rejects.toBe("EPASSWD");
+  });
+
+  it("joins responses split across multiple packets", async () => {
+    const connection = await connect(server);
+    const output: string[] = [];
+    const response = await connection.execute("cvarlist", (part) => {
+      output.push(part);
+    });
+    expect(response).toBe(longResponse);
+    expect(output.length).toBeGreaterThan(1);
+    expect(output.join("")).toBe(longResponse);
+  });
+
+  it("matches responses to their commands", async () => {
+    const connection = await connect(server);
+    const responses = await Promise.all([
+      connection.execute("cvarlist"),
+      connection.execute("echo"),
+      connection.execute("status"),
+    ]);
+    expect(responses).toEqual([longResponse, "echo\n", "hostname: test\n"]);
+  });
+
+  it("sends the commands of executeAll in a single write", async () => {
+    const connection = await connect(server);
+    const write = jest.spyOn(connection.socket as net.Socket, "write");
+    const responses = await Promise.all([
+      connection.executeAll(["echo", "status"]),
+      connection.execute("cvarlist"),
+    ]);
+    expect(write).toHaveBeenCalledTimes(2);
+    expect(responses).toEqual([["echo\n", "hostname: test\n"], longResponse]);
+  });
+
+  it("rejects executeAll when not connected", async () => {
+    const connection = new RconConnection();
+    await expect(connection.executeAll(["echo"])).rejects.toMatchObject({
+      code: "ENOTCONNECTED",
+    });
+  });
+});
@@ -0,0 +1,90 @@
+import { getVdfValue, parseVdf } from "../Vdf";
+
+// Shortened localconfig.vdf, with another "440" block before the app config
+const localConfig = `"UserLocalConfigStore"
+{
+	"friends"
+	{
+		"440"
+		{
+			"name"		"someone"
+		}
+	}
+	"Software"
+	{
+		"Valve"
+		{
+			"Steam"
+			{
+				"Apps"
+				{
+					"440"
+					{
+						"LaunchOptions"		"-usercon +rcon_password \\"a b\\" // c"
+					}
+				}
+			}
+		}
+	}
+}
+`;
+
+describe("parseVdf", () => {
+  it("parses nested blocks and escaped quotes", () => {
+    const vdf = parseVdf(localConfig);
+    expect(
+      getVdfValue(
+        vdf,
+        "UserLocalConfigStore",
+        "Software",
+        "Valve",
+        "Steam",
+        "apps",
+        "440",
+        "LaunchOptions"
+      )
+    ).toBe('-usercon +rcon_password "a b" // c');
+    expect(
+      getVdfValue(vdf, "UserLocalConfigStore", "friends", "440", "name")
+    ).toBe("someone");
+  });
+
+  it("parses both library folder formats", () => {
+    const oldFormat = parseVdf(`"LibraryFolders"
+{
+	"TimeNextStatsReport"		"1600000000"
+	"1"		"D:\\\\SteamLibrary"
+}`);
+    expect(getVdfValue(oldFormat, "libraryfolders", "1")).toBe(
+      "D:\\SteamLibrary"
+    );
+    const newFormat = parseVdf(`"libraryfolders"
+{
+	"1"
+	{
+		"path"		"D:\\\\SteamLibrary"
+		"apps" { "440" "12345" }
+	}
+}`);
+    expect(getVdfValue(newFormat, "libraryfolders", "1", "path")).toBe(
+      "D:\\SteamLibrary"
+    );
+  });
+
+  it("ignores comments, unquoted tokens and conditions", () => {
+    const vdf = parseVdf(`// comment
+root { key value [$WIN32] other "x" }`);
+    expect(vdf).toEqual({ root: { key: "value", other: "x" } });
+  });
+
+  it("rejects unbalanced braces", () => {
+    expect(() => parseVdf('"a" { "b" "c"')).toThrow();
+    expect(() => parseVdf('"a" "b" }')).toThrow();
+  });
+
+  it("returns undefined for missing keys", () => {
+    const vdf = parseVdf('"a" { "b" "c" }');
+    expect(getVdfValue(vdf, "a", "x")).toBeUndefined();
+    expect(getVdfValue(vdf, "a", "b", "c")).toBeUndefined();
+  });
+});
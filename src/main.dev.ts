@@ -24,6 +24,7 @@ import log from "electron-log";
 import cfg from "electron-cfg";
 
 import { loadPreferredTheme } from "./theme";
+import { Demo } from "./Demos";
 
 cfg.logger(log);
 
@@ -41,6 +42,24 @@ export default class AppUpdater {
 
 let mainWindow: BrowserWindow | null = null;
 
+// Relative paths are resolved against the directory the app was started in
+const getDemoPathsFromArgs = (args: string[], cwd = process.cwd()) =>
+  args
+    .filter((arg) => /\.dem$/i.test(arg))
+    .map((arg) => path.resolve(cwd, arg));
+
+// Demos opened through the OS before the window finished loading
+const pendingDemoPaths = getDemoPathsFromArgs(process.argv);
+
+const openDemo = (demoPath: string) => {
+  log.info(`Opening demo ${demoPath}`);
+  if (mainWindow && !mainWindow.webContents.isLoading()) {
+    mainWindow.webContents.send("open-demo", demoPath);
+  } else {
+    pendingDemoPaths.push(demoPath);
+  }
+};
+
 if (process.env.NODE_ENV === "production") {
   const sourceMapSupport = require("source-map-support");
   sourceMapSupport.install();
@@ -128,6 +147,7 @@ const createWindow = async () => {
       mainWindow.show();
       mainWindow.focus();
     }
+    pendingDemoPaths.splice(0).forEach(openDemo);
   });
 
   mainWindow.on("closed", () => {
@@ -159,7 +179,48 @@ app.on("window-all-closed", () => {
   }
 });
 
-app.whenReady().then(createWindow).catch(console.log);
+// Prints the header and events of the demos passed as arguments as JSON
+// and exits, without opening a window.
+const printDemosAsJson = async () => {
+  const results = await Promise.all(
+    getDemoPathsFromArgs(process.argv).map(async (demoPath) => {
+      try {
+        const { filename, header, events, birthtime, filesize } =
+          await Demo.create(demoPath);
+        return { filename, header, events, birthtime, filesize };
+      } catch (e) {
+        return { filename: demoPath, error: e.message };
+      }
+    })
+  );
+  process.stdout.write(`${JSON.stringify(results, null, 2)}\n`);
+  app.quit();
+};
+
+if (app.commandLine.hasSwitch("print-json")) {
+  log.transports.console.level = false;
+  app.whenReady().then(printDemosAsJson).catch(console.log);
+} else if (!app.requestSingleInstanceLock()) {
+  // Demos opened while DemoMan is running are passed to the running instance
+  app.quit();
+} else {
+  app.on("second-instance", (_event, argv, workingDirectory) => {
+    if (mainWindow) {
+      if (mainWindow.isMinimized()) {
+        mainWindow.restore();
+      }
+      mainWindow.focus();
+    }
+    getDemoPathsFromArgs(argv, workingDirectory).forEach(openDemo);
+  });
+  app.whenReady().then(createWindow).catch(console.log);
+}
+
+// macOS passes files opened with DemoMan through this event instead of argv
+app.on("open-file", (event, filePath) => {
+  event.preventDefault();
+  openDemo(filePath);
+});
 
 app.on("activate", () => {
   // On macOS it's common to re-create a window in the app when the
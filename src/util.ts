@@ -1,3 +1,5 @@
+import path from "path";
+
 function leftPadTwo(val: string) {
   return `00${val}`.slice(-Math.max(val.length, 2));
 }
@@ -29,3 +31,81 @@ export function formatFileSize(bytes: number): string {
   }
   return `${size.toFixed(1)} ${units[i]}`;
 }
+
+const STEAMID64_BASE = BigInt("76561197960265728");
+
+// Converts a SteamID3 like [U:1:12345] to a SteamID64
+export function steamId3To64(steamId3: string): string | undefined {
+  const match = /^\[U:1:(\d+)\]$/.exec(steamId3);
+  if (match === null) {
+    return undefined;
+  }
+  return (STEAMID64_BASE + BigInt(match[1])).toString();
+}
+
+export function getPlayerLinks(steamId64: string) {
+  return {
+    steam: `https://steamcommunity.com/profiles/${steamId64}`,
+    trendsTf: `https://trends.tf/player/${steamId64}`,
+    logsTf: `https://logs.tf/profile/${steamId64}`,
+    demosTf: `https://demos.tf/profiles/${steamId64}`,
+  };
+}
+
+/**
+ * Like Promise.allSettled(items.map(fn)), but runs at most `limit`
+ * calls of fn at the same time.
+ */
+export async function mapSettledConcurrently<T, R>(
+  items: T[],
+  limit: number,
+  fn: (item: T) => Promise<R>
+) {
+  const results: PromiseSettledResult<R>[] = new Array(items.length);
+  let next = 0;
+  const worker = async () => {
+    while (next < items.length) {
+      const index = next;
+      next += 1;
+      try {
+        // eslint-disable-next-line no-await-in-loop
+        results[index] = { status: "fulfilled", value: await fn(items[index]) };
+      } catch (reason) {
+        results[index] = { status: "rejected", reason };
+      }
+    }
+  };
+  await Promise.all(
+    Array.from({ length: Math.min(limit, items.length) }, worker)
+  );
+  return results;
+}
+
+/**
+ * Strips the Windows long path prefix (\\?\ or \\?\UNC\) from the path.
+ * Node adds it back where needed, but the path functions treat the prefix
+ * as part of the root, so prefixed paths never compare as relative to
+ * unprefixed ones.
+ */
+export function normalizeFilePath(filePath: string) {
+  if (process.platform !== "win32") {
+    return filePath;
+  }
+  return filePath
+    .replace(/^\\\\\?\\UNC\\/i, "\\\\")
+    .replace(/^\\\\\?\\/, "");
+}
+
+/**
+ * Returns a canonical form of the directory path to use as a key, so the
+ * same directory gets the same cache entries and preferences regardless
+ * of how it was entered. Strips the Windows long path prefix and
+ * trailing separators.
+ */
+export function normalizeDirPath(dirPath: string) {
+  const normalized = normalizeFilePath(path.resolve(dirPath));
+  if (process.platform === "win32") {
+    return normalized.toLowerCase();
+  }
+  return normalized;
+}